@@ -0,0 +1,44 @@
+//! Benches parsing and both parts of every registered day, on every one of its inputs
+use std::path::Path;
+
+use aoc_2022::{
+    runner::REGISTRY,
+    utils::{self, Input},
+};
+use criterion::{criterion_group, BatchSize, BenchmarkId, Criterion};
+
+fn benchmark(c: &mut Criterion) {
+    for entry in REGISTRY {
+        let mut group = c.benchmark_group(format!("day {:02}: {}", entry.day, entry.title));
+        let inputs = entry
+            .inputs(Path::new("inputs"))
+            .expect("could not list inputs");
+
+        for path in inputs {
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            let bytes = utils::load_bytes(&path.to_string_lossy()).expect("could not load input");
+            let parsed = entry
+                .parse(Input::from_bytes(bytes.clone()))
+                .expect("could not parse input");
+
+            // copying the input is part of the setup, so only the parsing itself is measured
+            group.bench_function(BenchmarkId::new("parse", &name), |b| {
+                b.iter_batched(
+                    || Input::from_bytes(bytes.clone()),
+                    |input| entry.parse(input),
+                    BatchSize::SmallInput,
+                )
+            });
+            group.bench_function(BenchmarkId::new("part 1", &name), |b| {
+                b.iter(|| entry.part_one(&parsed))
+            });
+            group.bench_function(BenchmarkId::new("part 2", &name), |b| {
+                b.iter(|| entry.part_two(&parsed))
+            });
+        }
+
+        group.finish();
+    }
+}
+
+criterion_group!(benches, benchmark);
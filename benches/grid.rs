@@ -0,0 +1,110 @@
+//! Compares the flat storage of `Grid` against the `Vec<Vec<T>>` it used to be built on
+//!
+//! Only the storage is measured, by allocating grids and sweeping over their neighbours. The
+//! grids are loaded from some of the puzzle inputs to get realistic sizes, but no day's code runs
+//! here.
+//!
+//! There is no before and after comparison of the days themselves: they only build on the flat
+//! `Grid` (day 14 doesn't use it at all since moving to `SparseGrid`), so there is no nested
+//! version of days 08, 12, 14 or 22 to bench against. Their `day NN` groups bench them on the
+//! flat storage only.
+use std::ops::Index;
+
+use aoc_2022::{
+    grid::{Coordinate, Grid},
+    utils,
+};
+use criterion::{black_box, criterion_group, Criterion};
+
+/// The nested layout `Grid` used before, kept here only to compare against
+struct Nested<T> {
+    rows: Vec<Vec<T>>,
+    n: usize,
+    m: usize,
+}
+
+impl<T: Copy> Nested<T> {
+    fn new(n: usize, m: usize, default: T) -> Self {
+        Self {
+            rows: vec![vec![default; m]; n],
+            n,
+            m,
+        }
+    }
+}
+
+impl<T> Index<Coordinate> for Nested<T> {
+    type Output = T;
+
+    fn index(&self, idx: Coordinate) -> &Self::Output {
+        &self.rows[idx.0 as usize][idx.1 as usize]
+    }
+}
+
+/// Sums the cardinal neighbours of every cell, which is how the days mostly walk their grids
+fn neighbour_sweep(grid: &impl Index<Coordinate, Output = u8>, n: usize, m: usize) -> usize {
+    let mut sum = 0;
+
+    for i in 0..n as isize {
+        for j in 0..m as isize {
+            for c in Coordinate(i, j).cardinal_neighbours() {
+                if (0..n as isize).contains(&c.0) && (0..m as isize).contains(&c.1) {
+                    sum += grid[c] as usize;
+                }
+            }
+        }
+    }
+
+    sum
+}
+
+/// Loads [path] as rows of bytes, padding short rows with spaces like day 22's board
+fn load_rows(path: &str) -> Vec<Vec<u8>> {
+    let lines = utils::load_input(path).expect("could not load input");
+    let lines: Vec<_> = lines.iter().take_while(|l| !l.is_empty()).collect();
+    let width = lines.iter().map(|l| l.len()).max().unwrap_or(0);
+
+    lines
+        .iter()
+        .map(|l| {
+            let mut row = l.as_bytes().to_vec();
+            row.resize(width, b' ');
+            row
+        })
+        .collect()
+}
+
+fn compare(c: &mut Criterion, rows: Vec<Vec<u8>>) {
+    let (n, m) = (rows.len(), rows[0].len());
+    let mut group = c.benchmark_group(format!("grid storage: {}x{}", n, m));
+
+    let nested = Nested {
+        rows: rows.clone(),
+        n,
+        m,
+    };
+    let flat = Grid::from(rows);
+
+    group.bench_function("nested new", |b| {
+        b.iter(|| Nested::new(black_box(n), black_box(m), 0_u8))
+    });
+    group.bench_function("flat new", |b| {
+        b.iter(|| Grid::new(black_box(n), black_box(m), 0_u8))
+    });
+    group.bench_function("nested neighbour sweep", |b| {
+        b.iter(|| neighbour_sweep(black_box(&nested), nested.n, nested.m))
+    });
+    group.bench_function("flat neighbour sweep", |b| {
+        b.iter(|| neighbour_sweep(black_box(&flat), flat.n, flat.m))
+    });
+    group.finish();
+}
+
+fn benchmark(c: &mut Criterion) {
+    // a small square, a wide and short one and a large ragged one
+    compare(c, load_rows("inputs/day_08"));
+    compare(c, load_rows("inputs/day_12"));
+    compare(c, load_rows("inputs/day_22"));
+}
+
+criterion_group!(benches, benchmark);
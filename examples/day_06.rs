@@ -1,11 +1,7 @@
-use aoc_2022::{day_06, solution::Solution, utils};
+use aoc_2022::{day_06::Day06, solution::Day, utils};
 
 fn main() {
     let lines = utils::load_input("inputs/day_06").expect("could not load input");
-    let parsed = day_06::parse_input(&lines).expect("could not parse input");
-    let solution = Solution {
-        part_one: day_06::part_one(&parsed),
-        part_two: day_06::part_two(&parsed),
-    };
+    let solution = Day06::solve(&lines).expect("could not parse input");
     println!("{}", solution);
 }
@@ -1,11 +1,7 @@
-use aoc_2022::{day_08, solution::Solution, utils};
+use aoc_2022::{day_08::Day08, solution::Day, utils};
 
 fn main() {
     let lines = utils::load_input("inputs/day_08").expect("could not load input");
-    let parsed = day_08::parse_input(&lines).expect("could not parse input");
-    let solution = Solution {
-        part_one: day_08::part_one(&parsed),
-        part_two: day_08::part_two(&parsed),
-    };
+    let solution = Day08::solve(&lines).expect("could not parse input");
     println!("{}", solution);
 }
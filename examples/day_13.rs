@@ -1,11 +1,7 @@
-use aoc_2022::{day_13, solution::Solution, utils};
+use aoc_2022::{day_13::Day13, solution::Day, utils};
 
 fn main() {
     let lines = utils::load_input("inputs/day_13").expect("could not load input");
-    let parsed = day_13::parse_input(&lines).expect("could not parse input");
-    let solution = Solution {
-        part_one: day_13::part_one(&parsed),
-        part_two: day_13::part_two(&parsed),
-    };
+    let solution = Day13::solve(&lines).expect("could not parse input");
     println!("{}", solution);
 }
@@ -1,11 +1,7 @@
-use aoc_2022::{day_15, solution::Solution, utils};
+use aoc_2022::{day_15::Day15, solution::Day, utils};
 
 fn main() {
     let lines = utils::load_input("inputs/day_15").expect("could not load input");
-    let parsed = day_15::parse_input(&lines).expect("could not parse input");
-    let solution = Solution {
-        part_one: day_15::part_one(&parsed),
-        part_two: day_15::part_two(&parsed),
-    };
+    let solution = Day15::solve(&lines).expect("could not parse input");
     println!("{}", solution);
 }
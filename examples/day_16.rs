@@ -0,0 +1,7 @@
+use aoc_2022::{day_16::Day16, solution::Day, utils};
+
+fn main() {
+    let lines = utils::load_input("inputs/day_16").expect("could not load input");
+    let solution = Day16::solve(&lines).expect("could not parse input");
+    println!("{}", solution);
+}
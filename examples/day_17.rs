@@ -1,11 +1,7 @@
-use aoc_2022::{day_17, solution::Solution, utils};
+use aoc_2022::{day_17::Day17, solution::Day, utils};
 
 fn main() {
     let lines = utils::load_input("inputs/day_17").expect("could not load input");
-    let parsed = day_17::parse_input(&lines).expect("could not parse input");
-    let solution = Solution {
-        part_one: day_17::part_one(&parsed),
-        part_two: day_17::part_two(&parsed),
-    };
+    let solution = Day17::solve(&lines).expect("could not parse input");
     println!("{}", solution);
 }
@@ -0,0 +1,7 @@
+use aoc_2022::{day_19::Day19, solution::Day, utils};
+
+fn main() {
+    let lines = utils::load_input("inputs/day_19").expect("could not load input");
+    let solution = Day19::solve(&lines).expect("could not parse input");
+    println!("{}", solution);
+}
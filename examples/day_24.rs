@@ -0,0 +1,7 @@
+use aoc_2022::{day_24::Day24, solution::Day, utils};
+
+fn main() {
+    let lines = utils::load_input("inputs/day_24").expect("could not load input");
+    let solution = Day24::solve(&lines).expect("could not parse input");
+    println!("{}", solution);
+}
@@ -1,8 +1,10 @@
-use anyhow::{anyhow, Error};
 use std::fmt;
-use std::ops::{Add, Sub};
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 use std::str::FromStr;
 
+use crate::parser::{integer, literal, pair, terminated, Parser};
+use crate::utils::ParseError;
+
 #[derive(Clone, Copy, Default, Eq, PartialEq, Hash)]
 pub struct Point3<T = i64> {
     pub x: T,
@@ -34,25 +36,13 @@ impl<T> FromStr for Point3<T>
 where
     T: FromStr,
 {
-    type Err = Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut iter = s.trim().split(',').map(|t| t.trim().parse::<T>());
+        let coordinate = || terminated(integer(), literal(","));
+        let (x, (y, z)) = pair(coordinate(), pair(coordinate(), integer())).parse_line(s)?;
 
-        Ok(Self {
-            x: iter
-                .next()
-                .ok_or_else(|| anyhow!("Not enough values for Point3"))?
-                .map_err(|_| anyhow!("Failed to parse value for Point3"))?,
-            y: iter
-                .next()
-                .ok_or_else(|| anyhow!("Not enough values for Point3"))?
-                .map_err(|_| anyhow!("Failed to parse value for Point3"))?,
-            z: iter
-                .next()
-                .ok_or_else(|| anyhow!("Not enough values for Point3"))?
-                .map_err(|_| anyhow!("Failed to parse value for Point3"))?,
-        })
+        Ok(Self { x, y, z })
     }
 }
 
@@ -78,29 +68,76 @@ impl fmt::Debug for Point3 {
     }
 }
 
-impl<T> Add<Vector3<T>> for Point3<T>
-where
-    T: Add<Output = T>,
-{
-    type Output = Self;
+/// Implements `Add`, `Sub` and their assigning versions between [$lhs] and [$rhs], component-wise
+macro_rules! impl_translation {
+    ($lhs:ident, $rhs:ident, $($f:ident),+) => {
+        impl<T: Add<Output = T>> Add<$rhs<T>> for $lhs<T> {
+            type Output = Self;
 
-    fn add(self, rhs: Vector3<T>) -> Self::Output {
-        Self {
-            x: self.x + rhs.x,
-            y: self.y + rhs.y,
-            z: self.z + rhs.z,
+            fn add(self, rhs: $rhs<T>) -> Self::Output {
+                Self { $($f: self.$f + rhs.$f),+ }
+            }
         }
-    }
+
+        impl<T: Sub<Output = T>> Sub<$rhs<T>> for $lhs<T> {
+            type Output = Self;
+
+            fn sub(self, rhs: $rhs<T>) -> Self::Output {
+                Self { $($f: self.$f - rhs.$f),+ }
+            }
+        }
+
+        impl<T: AddAssign> AddAssign<$rhs<T>> for $lhs<T> {
+            fn add_assign(&mut self, rhs: $rhs<T>) {
+                $(self.$f += rhs.$f;)+
+            }
+        }
+
+        impl<T: SubAssign> SubAssign<$rhs<T>> for $lhs<T> {
+            fn sub_assign(&mut self, rhs: $rhs<T>) {
+                $(self.$f -= rhs.$f;)+
+            }
+        }
+    };
+}
+
+/// Implements `Neg` and multiplication by a scalar for the vector [$v]
+macro_rules! impl_scaling {
+    ($v:ident, $($f:ident),+) => {
+        impl<T: Neg<Output = T>> Neg for $v<T> {
+            type Output = Self;
+
+            fn neg(self) -> Self::Output {
+                Self { $($f: -self.$f),+ }
+            }
+        }
+
+        impl<T: Copy + Mul<Output = T>> Mul<T> for $v<T> {
+            type Output = Self;
+
+            fn mul(self, rhs: T) -> Self::Output {
+                Self { $($f: self.$f * rhs),+ }
+            }
+        }
+
+        impl<T: Copy + MulAssign> MulAssign<T> for $v<T> {
+            fn mul_assign(&mut self, rhs: T) {
+                $(self.$f *= rhs;)+
+            }
+        }
+    };
 }
 
+impl_translation!(Point3, Vector3, x, y, z);
+
 impl<T> Sub for Point3<T>
 where
     T: Sub<Output = T>,
 {
-    type Output = Self;
+    type Output = Vector3<T>;
 
     fn sub(self, rhs: Self) -> Self::Output {
-        Self {
+        Vector3 {
             x: self.x - rhs.x,
             y: self.y - rhs.y,
             z: self.z - rhs.z,
@@ -108,22 +145,77 @@ where
     }
 }
 
-impl<T> Sub<Vector3<T>> for Point3<T>
+impl Point3 {
+    pub fn manhattan_distance(&self, other: &Self) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y) + self.z.abs_diff(other.z)
+    }
+
+    /// The number of king moves between 2 points, where diagonals count as 1 step
+    pub fn chebyshev_distance(&self, other: &Self) -> u64 {
+        self.x
+            .abs_diff(other.x)
+            .max(self.y.abs_diff(other.y))
+            .max(self.z.abs_diff(other.z))
+    }
+
+    pub fn euclidean_distance(&self, other: &Self) -> f64 {
+        let d = *self - *other;
+        (d.dot(&d) as f64).sqrt()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+pub struct Vector2<T = i64> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Vector2<T> {
+    pub fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T> Vector2<T>
 where
-    T: Sub<Output = T>,
+    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
 {
-    type Output = Self;
+    pub fn dot(&self, other: &Self) -> T {
+        self.x * other.x + self.y * other.y
+    }
 
-    fn sub(self, rhs: Vector3<T>) -> Self::Output {
-        Self {
-            x: self.x - rhs.x,
-            y: self.y - rhs.y,
-            z: self.z - rhs.z,
-        }
+    /// The z component of the 3D cross product, which is positive if [other] is counterclockwise
+    /// from [self]
+    pub fn cross(&self, other: &Self) -> T {
+        self.x * other.y - self.y * other.x
     }
 }
 
-#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+impl<T> Vector2<T>
+where
+    T: Copy + Neg<Output = T>,
+{
+    /// Rotates by 90° counterclockwise, with x pointing right and y pointing up
+    pub fn rotate_left(&self) -> Self {
+        Self::new(-self.y, self.x)
+    }
+
+    /// Rotates by 90° clockwise, with x pointing right and y pointing up
+    pub fn rotate_right(&self) -> Self {
+        Self::new(self.y, -self.x)
+    }
+}
+
+impl<T> From<(T, T)> for Vector2<T> {
+    fn from(v: (T, T)) -> Self {
+        Self { x: v.0, y: v.1 }
+    }
+}
+
+impl_translation!(Vector2, Vector2, x, y);
+impl_scaling!(Vector2, x, y);
+
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
 pub struct Vector3<T = i64> {
     pub x: T,
     pub y: T,
@@ -136,6 +228,43 @@ impl<T> Vector3<T> {
     }
 }
 
+impl<T> Vector3<T>
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    pub fn dot(&self, other: &Self) -> T {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    pub fn cross(&self, other: &Self) -> Self {
+        Self::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+}
+
+impl<T> Vector3<T>
+where
+    T: Copy + Neg<Output = T>,
+{
+    /// Rotates by 90° around the x axis, counterclockwise when looking down the axis
+    pub fn rotate_x(&self) -> Self {
+        Self::new(self.x, -self.z, self.y)
+    }
+
+    /// Rotates by 90° around the y axis, counterclockwise when looking down the axis
+    pub fn rotate_y(&self) -> Self {
+        Self::new(self.z, self.y, -self.x)
+    }
+
+    /// Rotates by 90° around the z axis, counterclockwise when looking down the axis
+    pub fn rotate_z(&self) -> Self {
+        Self::new(-self.y, self.x, self.z)
+    }
+}
+
 impl<T> From<(T, T, T)> for Vector3<T> {
     fn from(v: (T, T, T)) -> Self {
         Self {
@@ -145,3 +274,57 @@ impl<T> From<(T, T, T)> for Vector3<T> {
         }
     }
 }
+
+impl_translation!(Vector3, Vector3, x, y, z);
+impl_scaling!(Vector3, x, y, z);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn point3_test() {
+        let a = Point3::new(1, 2, 3);
+        let b = Point3::new(4, -2, 3);
+
+        assert_eq!(b - a, Vector3::new(3, -4, 0));
+        assert_eq!(a + (b - a), b);
+        assert_eq!(b - (b - a), a);
+        assert_eq!(a.manhattan_distance(&b), 7);
+        assert_eq!(a.chebyshev_distance(&b), 4);
+        assert_eq!(a.euclidean_distance(&b), 5.0);
+
+        let mut c = a;
+        c += Vector3::new(1, 1, 1);
+        c -= Vector3::new(0, 0, 2);
+        assert_eq!(c, Point3::new(2, 3, 2));
+    }
+
+    #[test]
+    fn vector_test() {
+        let v = Vector2::new(3, 1);
+        assert_eq!(v + Vector2::new(1, 1), Vector2::new(4, 2));
+        assert_eq!(-v * 2, Vector2::new(-6, -2));
+        assert_eq!(v.dot(&Vector2::new(2, -6)), 0);
+        assert_eq!(v.cross(&v.rotate_left()), 10);
+        assert_eq!(v.rotate_left().rotate_left(), -v);
+        assert_eq!(v.rotate_left().rotate_right(), v);
+
+        let (x, y, z) = (
+            Vector3::new(1, 0, 0),
+            Vector3::new(0, 1, 0),
+            Vector3::new(0, 0, 1),
+        );
+        assert_eq!(x.cross(&y), z);
+        assert_eq!(y.cross(&x), -z);
+        assert_eq!(x.rotate_z(), y);
+        assert_eq!(y.rotate_x(), z);
+        assert_eq!(z.rotate_y(), x);
+        assert_eq!((x * 2 - y).dot(&Vector3::new(3, 4, 5)), 2);
+
+        let mut w = Vector3::new(1, 2, 3);
+        w *= 3;
+        w -= Vector3::new(3, 3, 3);
+        assert_eq!(w, Vector3::new(0, 3, 6));
+    }
+}
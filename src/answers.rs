@@ -0,0 +1,306 @@
+use std::{collections::BTreeMap, fmt, fs, num::NonZeroUsize, str::FromStr};
+
+use anyhow::{Context, Error, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::runner::{self, Answer, Part};
+
+/// The expected answers for a single input. A part without an expected answer is reported as
+/// missing when verifying.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct Expected {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part_one: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part_two: Option<serde_json::Value>,
+}
+
+impl Expected {
+    /// Returns `true` if neither part has an expected answer yet
+    pub fn is_empty(&self) -> bool {
+        self.part_one.is_none() && self.part_two.is_none()
+    }
+}
+
+/// Expected answers keyed by day, then by input path
+///
+/// It looks like:
+///   {
+///     "12": {
+///       "inputs/day_12.example": { "part_one": 31, "part_two": 29 }
+///     }
+///   }
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct Answers(BTreeMap<u8, BTreeMap<String, Expected>>);
+
+impl Answers {
+    pub fn load(path: &str) -> Result<Self> {
+        fs::read_to_string(path)
+            .with_context(|| format!("could not load {}", path))?
+            .parse()
+            .with_context(|| format!("could not parse {}", path))
+    }
+
+    /// Adds an input without any expected answers, unless the input is already listed. Returns
+    /// whether it was added.
+    pub fn add_stub(&mut self, day: u8, input: &str) -> bool {
+        let inputs = self.0.entry(day).or_default();
+
+        if inputs.contains_key(input) {
+            return false;
+        }
+
+        inputs.insert(input.to_owned(), Expected::default());
+        true
+    }
+}
+
+/// Prints the answers as pretty json, the same way `answers.json` is written
+impl fmt::Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let json = serde_json::to_string_pretty(self).map_err(|_| fmt::Error)?;
+        f.write_str(&json)
+    }
+}
+
+impl FromStr for Answers {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(serde_json::from_str(s)?)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Status {
+    Pass,
+    Fail { expected: String, actual: String },
+    Missing,
+    Error(String),
+}
+
+impl Status {
+    fn of(expected: Option<&serde_json::Value>, actual: Option<&Answer>) -> Self {
+        let (expected, actual) = match (expected, actual) {
+            (Some(e), Some(a)) => (e, a.to_string()),
+            _ => return Self::Missing,
+        };
+
+        // answers are compared by their display form, so `"CMZ"` and `123` can both be written
+        // as plain json values
+        let expected = match expected {
+            serde_json::Value::String(s) => s.to_owned(),
+            other => other.to_string(),
+        };
+
+        if expected == actual {
+            Self::Pass
+        } else {
+            Self::Fail { expected, actual }
+        }
+    }
+
+    /// Returns `true` if this status should cause verification to fail
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Self::Fail { .. } | Self::Error(_))
+    }
+}
+
+/// The result of verifying one part of one day against one input
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Check {
+    pub day: u8,
+    pub input: String,
+    pub part: Option<Part>,
+    pub status: Status,
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self.status {
+            Status::Pass => "PASS",
+            Status::Fail { .. } => "FAIL",
+            Status::Missing => "MISS",
+            Status::Error(_) => "ERR ",
+        };
+
+        write!(f, "{} day {:02}", label, self.day)?;
+
+        match self.part {
+            Some(Part::One) => write!(f, " part 1")?,
+            Some(Part::Two) => write!(f, " part 2")?,
+            None => {}
+        }
+
+        write!(f, " ({})", self.input)?;
+
+        match &self.status {
+            Status::Fail { expected, actual } => {
+                write!(f, ": expected {:?}, got {:?}", expected, actual)
+            }
+            Status::Error(e) => write!(f, ": {}", e),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Runs every registered day against every input listed for it in [answers], on up to [jobs]
+/// threads
+///
+/// Days without any listed inputs are reported as missing, and listed days without a solution
+/// are reported as errors.
+pub fn verify(answers: &Answers, jobs: NonZeroUsize) -> Vec<Check> {
+    let mut checks = Vec::default();
+    let mut runs = Vec::default();
+
+    for entry in runner::REGISTRY {
+        match answers.0.get(&entry.day) {
+            Some(inputs) if !inputs.is_empty() => {
+                for (input, expected) in inputs {
+                    // there's nothing to check yet, so don't bother running the day
+                    if expected.is_empty() {
+                        checks.push(Check {
+                            day: entry.day,
+                            input: input.to_owned(),
+                            part: None,
+                            status: Status::Missing,
+                        });
+                    } else {
+                        runs.push((entry, input.to_owned()));
+                    }
+                }
+            }
+            _ => checks.push(Check {
+                day: entry.day,
+                input: entry.default_input(),
+                part: None,
+                status: Status::Missing,
+            }),
+        }
+    }
+
+    let reports = runner::run_all(&runs, None, jobs);
+
+    for ((entry, input), report) in runs.into_iter().zip(reports) {
+        let expected = &answers.0[&entry.day][&input];
+
+        let report = match report {
+            Ok(report) => report,
+            Err(e) => {
+                checks.push(Check {
+                    day: entry.day,
+                    input,
+                    part: None,
+                    status: Status::Error(format!("{:#}", e)),
+                });
+                continue;
+            }
+        };
+
+        checks.push(Check {
+            day: entry.day,
+            input: input.clone(),
+            part: Some(Part::One),
+            status: Status::of(expected.part_one.as_ref(), report.part_one.as_ref()),
+        });
+        checks.push(Check {
+            day: entry.day,
+            input,
+            part: Some(Part::Two),
+            status: Status::of(expected.part_two.as_ref(), report.part_two.as_ref()),
+        });
+    }
+
+    // the missing days were checked first, so put everything back in order
+    checks.sort_by_key(|c| c.day);
+
+    for (day, inputs) in &answers.0 {
+        if runner::find(*day).is_some() {
+            continue;
+        }
+
+        for input in inputs.keys() {
+            checks.push(Check {
+                day: *day,
+                input: input.to_owned(),
+                part: None,
+                status: Status::Error("no solution for this day".to_owned()),
+            });
+        }
+    }
+
+    checks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_test() {
+        let answer = Answer::new(&31);
+        let expected = serde_json::json!(31);
+        assert_eq!(Status::of(Some(&expected), Some(&answer)), Status::Pass);
+
+        let expected = serde_json::json!("31");
+        assert_eq!(Status::of(Some(&expected), Some(&answer)), Status::Pass);
+
+        let expected = serde_json::json!(30);
+        assert_eq!(
+            Status::of(Some(&expected), Some(&answer)),
+            Status::Fail {
+                expected: "30".to_owned(),
+                actual: "31".to_owned()
+            }
+        );
+
+        assert_eq!(Status::of(None, Some(&answer)), Status::Missing);
+    }
+
+    #[test]
+    fn stub_test() {
+        let mut answers: Answers = r#"{ "12": { "inputs/day_12": { "part_one": 456 } } }"#
+            .parse()
+            .expect("could not parse answers");
+
+        assert!(answers.add_stub(12, "inputs/day_12.example"));
+        assert!(answers.add_stub(3, "inputs/day_03"));
+        assert!(!answers.add_stub(12, "inputs/day_12"));
+
+        let json = answers.to_string();
+        assert!(json.find("\"3\"") < json.find("\"12\""));
+        assert!(json.contains("\"inputs/day_12.example\": {}"));
+        assert_eq!(json.parse::<Answers>().expect("could not reparse"), answers);
+    }
+
+    #[test]
+    fn verify_test() {
+        let answers: Answers = r#"{
+            "12": {
+                "inputs/day_12.example": { "part_one": 31, "part_two": 30 },
+                "inputs/day_12.missing": {}
+            },
+            "26": { "inputs/day_26": { "part_one": 1 } }
+        }"#
+        .parse()
+        .expect("could not parse answers");
+
+        let checks = verify(&answers, runner::default_jobs());
+        let find = |day, part| {
+            checks
+                .iter()
+                .find(|c| c.day == day && c.part == part)
+                .map(|c| c.status.clone())
+        };
+
+        assert_eq!(find(12, Some(Part::One)), Some(Status::Pass));
+        assert!(matches!(
+            find(12, Some(Part::Two)),
+            Some(Status::Fail { .. })
+        ));
+        assert_eq!(find(1, None), Some(Status::Missing));
+        assert_eq!(find(12, None), Some(Status::Missing));
+        assert!(matches!(find(26, None), Some(Status::Error(_))));
+    }
+}
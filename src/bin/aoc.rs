@@ -0,0 +1,266 @@
+use std::{env, num::NonZeroUsize, path::Path, process::ExitCode};
+
+use anyhow::{anyhow, bail, Result};
+use aoc_2022::{
+    answers::{self, Answers, Status},
+    runner::{self, Entry, Part},
+    scaffold::Scaffold,
+};
+
+const USAGE: &str = "\
+usage:
+    aoc run <day|all> [--part <1|2>] [--input <path>] [--jobs <n>]
+    aoc verify [--answers <path>] [--jobs <n>]
+    aoc new <day> [--title <title>]
+
+options:
+    --part <1|2>        only solve the given part
+    --input <path>      use the given input instead of inputs/day_NN, - for stdin (single day only)
+    --answers <path>    check against the given answers instead of answers.json
+    --jobs <n>          use at most n threads, defaults to the number of cores
+    --title <title>     the title of the puzzle, used in the reports and benches";
+
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+struct RunArgs {
+    day: Option<u8>,
+    part: Option<Part>,
+    input: Option<String>,
+    jobs: Option<NonZeroUsize>,
+}
+
+impl RunArgs {
+    fn parse(args: &[String]) -> Result<Self> {
+        let mut iter = args.iter();
+
+        let day = match iter.next().map(|s| s.as_str()) {
+            Some("all") => None,
+            Some(day) => Some(day.parse().map_err(|_| anyhow!("Invalid day: {}", day))?),
+            None => bail!("Missing day"),
+        };
+
+        let mut run_args = Self {
+            day,
+            ..Default::default()
+        };
+
+        while let Some(arg) = iter.next() {
+            let value = iter
+                .next()
+                .ok_or_else(|| anyhow!("Missing value for {}", arg))?;
+
+            match arg.as_str() {
+                "--part" => run_args.part = Some(value.parse()?),
+                "--input" => run_args.input = Some(value.to_owned()),
+                "--jobs" => run_args.jobs = Some(parse_jobs(value)?),
+                _ => bail!("Unknown option: {}", arg),
+            }
+        }
+
+        if run_args.day.is_none() && run_args.input.is_some() {
+            bail!("--input can only be used with a single day");
+        }
+
+        Ok(run_args)
+    }
+}
+
+/// Parses the value of `--jobs`, which has to be at least 1
+fn parse_jobs(value: &str) -> Result<NonZeroUsize> {
+    value
+        .parse()
+        .map_err(|_| anyhow!("Invalid number of jobs: {}", value))
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+struct VerifyArgs {
+    answers: String,
+    jobs: Option<NonZeroUsize>,
+}
+
+impl VerifyArgs {
+    fn parse(args: &[String]) -> Result<Self> {
+        let mut verify_args = Self {
+            answers: "answers.json".to_owned(),
+            jobs: None,
+        };
+        let mut iter = args.iter();
+
+        while let Some(arg) = iter.next() {
+            let value = iter
+                .next()
+                .ok_or_else(|| anyhow!("Missing value for {}", arg))?;
+
+            match arg.as_str() {
+                "--answers" => verify_args.answers = value.to_owned(),
+                "--jobs" => verify_args.jobs = Some(parse_jobs(value)?),
+                _ => bail!("Unknown option: {}", arg),
+            }
+        }
+
+        Ok(verify_args)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+struct NewArgs {
+    day: u8,
+    title: String,
+}
+
+impl NewArgs {
+    fn parse(args: &[String]) -> Result<Self> {
+        let mut iter = args.iter();
+
+        let day = iter.next().ok_or_else(|| anyhow!("Missing day"))?;
+        let mut new_args = Self {
+            day: day.parse().map_err(|_| anyhow!("Invalid day: {}", day))?,
+            title: "REPLACE ME".to_owned(),
+        };
+
+        while let Some(arg) = iter.next() {
+            let value = iter
+                .next()
+                .ok_or_else(|| anyhow!("Missing value for {}", arg))?;
+
+            match arg.as_str() {
+                "--title" => new_args.title = value.to_owned(),
+                _ => bail!("Unknown option: {}", arg),
+            }
+        }
+
+        Ok(new_args)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+enum Command {
+    Run(RunArgs),
+    Verify(VerifyArgs),
+    New(NewArgs),
+    Help,
+}
+
+impl Command {
+    fn parse(args: &[String]) -> Result<Self> {
+        match args.first().map(|s| s.as_str()) {
+            Some("run") => Ok(Self::Run(RunArgs::parse(&args[1..])?)),
+            Some("verify") => Ok(Self::Verify(VerifyArgs::parse(&args[1..])?)),
+            Some("new") => Ok(Self::New(NewArgs::parse(&args[1..])?)),
+            Some("help") | Some("--help") | Some("-h") => Ok(Self::Help),
+            Some(command) => bail!("Unknown command: {}", command),
+            None => bail!("Missing command"),
+        }
+    }
+}
+
+fn run(run_args: &RunArgs) -> Result<()> {
+    let runs: Vec<(&Entry, String)> = match run_args.day {
+        Some(day) => {
+            let entry = runner::find(day).ok_or_else(|| anyhow!("No solution for day {}", day))?;
+            let input = run_args
+                .input
+                .clone()
+                .unwrap_or_else(|| entry.default_input());
+            vec![(entry, input)]
+        }
+        // days whose input hasn't been downloaded yet are skipped rather than failed
+        None => runner::REGISTRY
+            .iter()
+            .map(|entry| (entry, entry.default_input()))
+            .filter(|(entry, input)| {
+                let exists = Path::new(input).exists();
+                if !exists {
+                    eprintln!("skipping day {:02}: {} is missing", entry.day, input);
+                }
+                exists
+            })
+            .collect(),
+    };
+
+    let jobs = run_args.jobs.unwrap_or_else(runner::default_jobs);
+    let mut failed = 0;
+
+    for report in runner::run_all(&runs, run_args.part, jobs) {
+        match report {
+            Ok(report) => println!("{}", report),
+            // a single day reports its error like any other failed command
+            Err(e) if runs.len() == 1 => return Err(e),
+            Err(e) => {
+                eprintln!("error: {:#}", e);
+                failed += 1;
+            }
+        }
+    }
+
+    if failed > 0 {
+        bail!("{} day(s) failed", failed);
+    }
+
+    Ok(())
+}
+
+fn verify(verify_args: &VerifyArgs) -> Result<()> {
+    let answers = Answers::load(&verify_args.answers)?;
+    let jobs = verify_args.jobs.unwrap_or_else(runner::default_jobs);
+    let checks = answers::verify(&answers, jobs);
+
+    for check in &checks {
+        println!("{}", check);
+    }
+
+    let count = |f: fn(&Status) -> bool| checks.iter().filter(|c| f(&c.status)).count();
+    let passed = count(|s| *s == Status::Pass);
+    let failed = count(Status::is_failure);
+    let missing = count(|s| *s == Status::Missing);
+
+    println!(
+        "\n{} passed, {} failed, {} missing",
+        passed, failed, missing
+    );
+
+    if failed > 0 {
+        bail!("{} check(s) failed", failed);
+    }
+
+    Ok(())
+}
+
+fn new(new_args: &NewArgs) -> Result<()> {
+    let scaffold = Scaffold::new(Path::new("."), new_args.day, &new_args.title)?;
+
+    for path in scaffold.write()? {
+        println!("wrote {}", path.display());
+    }
+
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let command = match Command::parse(&args) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("error: {:#}\n\n{}", e, USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let result = match command {
+        Command::Run(run_args) => run(&run_args),
+        Command::Verify(verify_args) => verify(&verify_args),
+        Command::New(new_args) => new(&new_args),
+        Command::Help => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {:#}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
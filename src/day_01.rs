@@ -1,37 +1,35 @@
-use std::num::ParseIntError;
-
 use anyhow::Result;
 
+use crate::parser::{self, integer};
+use crate::solution::Day;
+use crate::utils::{self, ParseError};
+
 #[derive(Debug, Clone, Copy, Default, Eq, PartialEq, PartialOrd, Ord)]
 pub struct Elf {
     calories: usize,
 }
 
 impl TryFrom<&[String]> for Elf {
-    type Error = ParseIntError;
+    type Error = ParseError;
 
     fn try_from(value: &[String]) -> Result<Self, Self::Error> {
-        let calories: Result<Vec<usize>, _> = value.iter().map(|v| v.parse::<usize>()).collect();
+        let calories: Vec<usize> = parser::each_line(value, integer())?;
+
         Ok(Self {
-            calories: calories?.iter().sum(),
+            calories: calories.iter().sum(),
         })
     }
 }
 
-pub fn parse_input(lines: Vec<String>) -> Result<Vec<Elf>> {
-    let elves = lines
-        .split(|line| line == "")
-        .map(|group| Elf::try_from(group))
-        .collect::<Result<Vec<_>, _>>()?;
-
-    Ok(elves)
+pub fn parse_input(lines: &[String]) -> Result<Vec<Elf>> {
+    Ok(utils::parse_blocks(lines)?)
 }
 
-pub fn part_one(parsed: &Vec<Elf>) -> usize {
+pub fn part_one(parsed: &[Elf]) -> usize {
     parsed.iter().max().map(|elf| elf.calories).unwrap_or(0)
 }
 
-pub fn part_two(parsed: &Vec<Elf>) -> usize {
+pub fn part_two(parsed: &[Elf]) -> usize {
     let mut a = usize::MIN;
     let mut b = usize::MIN;
     let mut c = usize::MIN;
@@ -52,6 +50,29 @@ pub fn part_two(parsed: &Vec<Elf>) -> usize {
     a + b + c
 }
 
+pub struct Day01;
+
+impl Day for Day01 {
+    const DAY: u8 = 1;
+    const TITLE: &'static str = "calorie counting";
+
+    type Parsed = Vec<Elf>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(lines: &[String]) -> Result<Self::Parsed> {
+        parse_input(lines)
+    }
+
+    fn part_one(parsed: &Self::Parsed) -> Self::PartOne {
+        part_one(parsed)
+    }
+
+    fn part_two(parsed: &Self::Parsed) -> Self::PartTwo {
+        part_two(parsed)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::utils;
@@ -61,14 +82,14 @@ mod tests {
     #[test]
     fn part_one_test() {
         let lines = utils::load_input("inputs/day_01.example").expect("could not load input");
-        let parsed = parse_input(lines).expect("could not parse input");
+        let parsed = parse_input(&lines).expect("could not parse input");
         assert_eq!(part_one(&parsed), 24000);
     }
 
     #[test]
     fn part_two_test() {
         let lines = utils::load_input("inputs/day_01.example").expect("could not load input");
-        let parsed = parse_input(lines).expect("could not parse input");
+        let parsed = parse_input(&lines).expect("could not parse input");
         assert_eq!(part_two(&parsed), 45000);
     }
 }
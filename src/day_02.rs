@@ -1,6 +1,10 @@
-use anyhow::{anyhow, Error, Result};
+use anyhow::Result;
 use std::str::FromStr;
 
+use crate::parser::{literal, pair, terminated, try_map, word, Parser};
+use crate::solution::Day;
+use crate::utils::{self, ParseError};
+
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 enum Shape {
     Rock,
@@ -25,14 +29,14 @@ impl Shape {
 }
 
 impl FromStr for Shape {
-    type Err = Error;
+    type Err = ParseError;
 
-    fn from_str(s: &str) -> Result<Self> {
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "A" | "X" => Ok(Self::Rock),
             "B" | "Y" => Ok(Self::Paper),
             "C" | "Z" => Ok(Self::Scissors),
-            _ => Err(anyhow!("Invalid shape code: {}", s)),
+            _ => Err(ParseError::new("a shape code (A, B, C, X, Y or Z)")),
         }
     }
 }
@@ -60,14 +64,14 @@ impl Outcome {
 }
 
 impl FromStr for Outcome {
-    type Err = Error;
+    type Err = ParseError;
 
-    fn from_str(s: &str) -> Result<Self> {
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "X" => Ok(Self::Lose),
             "Y" => Ok(Self::Draw),
             "Z" => Ok(Self::Win),
-            _ => Err(anyhow!("Invalid outcome code: {}", s)),
+            _ => Err(ParseError::new("an outcome code (X, Y or Z)")),
         }
     }
 }
@@ -97,44 +101,67 @@ pub struct Rounds {
 }
 
 impl FromStr for Rounds {
-    type Err = Error;
-
-    fn from_str(s: &str) -> Result<Self> {
-        s.split_once(" ")
-            .and_then(|(a, b)| {
-                match (Shape::from_str(a), Shape::from_str(b), Outcome::from_str(b)) {
-                    (Ok(opponent_shape), Ok(my_shape), Ok(desired_outcome)) => Some(Rounds {
-                        shape_round: Round {
-                            opponent_shape,
-                            my_shape,
-                        },
-                        outcome_round: Round {
-                            opponent_shape,
-                            my_shape: desired_outcome.required_shape(opponent_shape),
-                        },
-                    }),
-                    _ => None,
-                }
-            })
-            .ok_or_else(|| anyhow!("Invalid input for Round: {}", s))
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // the second code is read both as a shape and as an outcome
+        let codes = pair(
+            terminated(try_map(word(), Shape::from_str), literal(" ")),
+            try_map(word(), |b| Ok((Shape::from_str(b)?, Outcome::from_str(b)?))),
+        );
+        let (opponent_shape, (my_shape, desired_outcome)) = codes.parse_line(s)?;
+
+        Ok(Rounds {
+            shape_round: Round {
+                opponent_shape,
+                my_shape,
+            },
+            outcome_round: Round {
+                opponent_shape,
+                my_shape: desired_outcome.required_shape(opponent_shape),
+            },
+        })
     }
 }
 
-pub fn parse_input(lines: Vec<String>) -> Result<Vec<Rounds>> {
-    lines.iter().map(|l| Rounds::from_str(l)).collect()
+pub fn parse_input(lines: &[String]) -> Result<Vec<Rounds>> {
+    Ok(utils::parse_lines(lines)?)
 }
 
-pub fn part_one(parsed: &Vec<Rounds>) -> usize {
+pub fn part_one(parsed: &[Rounds]) -> usize {
     parsed.iter().map(|rounds| rounds.shape_round.score()).sum()
 }
 
-pub fn part_two(parsed: &Vec<Rounds>) -> usize {
+pub fn part_two(parsed: &[Rounds]) -> usize {
     parsed
         .iter()
         .map(|rounds| rounds.outcome_round.score())
         .sum()
 }
 
+pub struct Day02;
+
+impl Day for Day02 {
+    const DAY: u8 = 2;
+    const TITLE: &'static str = "rock paper scissors";
+
+    type Parsed = Vec<Rounds>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(lines: &[String]) -> Result<Self::Parsed> {
+        parse_input(lines)
+    }
+
+    fn part_one(parsed: &Self::Parsed) -> Self::PartOne {
+        part_one(parsed)
+    }
+
+    fn part_two(parsed: &Self::Parsed) -> Self::PartTwo {
+        part_two(parsed)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::utils;
@@ -144,14 +171,14 @@ mod tests {
     #[test]
     fn part_one_test() {
         let lines = utils::load_input("inputs/day_02.example").expect("could not load input");
-        let parsed = parse_input(lines).expect("could not parse input");
+        let parsed = parse_input(&lines).expect("could not parse input");
         assert_eq!(part_one(&parsed), 15);
     }
 
     #[test]
     fn part_two_test() {
         let lines = utils::load_input("inputs/day_02.example").expect("could not load input");
-        let parsed = parse_input(lines).expect("could not parse input");
+        let parsed = parse_input(&lines).expect("could not parse input");
         assert_eq!(part_two(&parsed), 12);
     }
 }
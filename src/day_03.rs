@@ -1,6 +1,9 @@
 use std::str::FromStr;
 
-use anyhow::{anyhow, Error, Result};
+use anyhow::Result;
+
+use crate::solution::Day;
+use crate::utils::{self, ParseError};
 
 fn char_to_priority(c: &char) -> usize {
     if c.is_lowercase() {
@@ -41,28 +44,25 @@ impl RuckSack {
 }
 
 impl FromStr for RuckSack {
-    type Err = Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let len = s.len();
-        let mut chars = s.chars();
         let mut first_compartment = 0;
         let mut second_compartment = 0;
 
-        for _ in 0..len / 2 {
-            let c = chars
-                .next()
-                .ok_or_else(|| anyhow!("Not enough items in rucksack"))?;
-            let p = char_to_bitmask(&c);
-            first_compartment |= p;
-        }
+        for (i, c) in s.char_indices() {
+            if !c.is_ascii_alphabetic() {
+                return Err(ParseError::new("an item (a-z or A-Z)").at_offset(i));
+            }
 
-        for _ in len / 2..len {
-            let c = chars
-                .next()
-                .ok_or_else(|| anyhow!("Not enough items in rucksack"))?;
             let p = char_to_bitmask(&c);
-            second_compartment |= p;
+
+            if i < len / 2 {
+                first_compartment |= p;
+            } else {
+                second_compartment |= p;
+            }
         }
 
         Ok(RuckSack {
@@ -73,17 +73,17 @@ impl FromStr for RuckSack {
 }
 
 pub fn parse_input(lines: &[String]) -> Result<Vec<RuckSack>> {
-    lines.iter().map(|l| RuckSack::from_str(l)).collect()
+    Ok(utils::parse_lines(lines)?)
 }
 
-pub fn part_one(parsed: &Vec<RuckSack>) -> usize {
+pub fn part_one(parsed: &[RuckSack]) -> usize {
     parsed
         .iter()
         .map(|rucksack| rucksack.common_item_priority().unwrap_or(0))
         .sum()
 }
 
-pub fn part_two(parsed: &Vec<RuckSack>) -> usize {
+pub fn part_two(parsed: &[RuckSack]) -> usize {
     parsed
         .chunks(3)
         .map(|rucksacks| {
@@ -94,6 +94,29 @@ pub fn part_two(parsed: &Vec<RuckSack>) -> usize {
         .sum()
 }
 
+pub struct Day03;
+
+impl Day for Day03 {
+    const DAY: u8 = 3;
+    const TITLE: &'static str = "rucksack reorganization";
+
+    type Parsed = Vec<RuckSack>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(lines: &[String]) -> Result<Self::Parsed> {
+        parse_input(lines)
+    }
+
+    fn part_one(parsed: &Self::Parsed) -> Self::PartOne {
+        part_one(parsed)
+    }
+
+    fn part_two(parsed: &Self::Parsed) -> Self::PartTwo {
+        part_two(parsed)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::utils;
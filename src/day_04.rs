@@ -1,52 +1,31 @@
 use std::str::FromStr;
 
-use anyhow::{anyhow, Error, Result};
-
-#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
-struct Assignment {
-    low: usize,
-    high: usize,
-}
-
-impl Assignment {
-    /// Returns `true` if the assignment fully contains the other assignment. Returns `false`
-    /// otherwise.
-    fn fully_contains(&self, other: &Assignment) -> bool {
-        self.low <= other.low && self.high >= other.high
-    }
-
-    /// Returns `true` if the assignment overlaps the other assignment. Returns `false` otherwise.
-    fn overlaps(&self, other: &Assignment) -> bool {
-        !(self.low > other.high || other.low > self.high)
-    }
-}
-
-impl FromStr for Assignment {
-    type Err = Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (low, high) = s
-            .split_once("-")
-            .ok_or_else(|| anyhow!("Invalid assignment input"))?;
-
-        Ok(Self {
-            low: low.parse::<usize>()?,
-            high: high.parse::<usize>()?,
-        })
-    }
+use anyhow::Result;
+
+use crate::intervals::Interval;
+use crate::parser::{integer, literal, map, pair, terminated, Parser};
+use crate::solution::Day;
+use crate::utils::{self, ParseError};
+
+/// Parses an assignment like `2-4`
+fn assignment<'a>() -> impl Parser<'a, Interval<usize>> {
+    map(
+        pair(terminated(integer(), literal("-")), integer()),
+        |(low, high)| Interval::new(low, high),
+    )
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Assignments {
-    first: Assignment,
-    second: Assignment,
+    first: Interval<usize>,
+    second: Interval<usize>,
 }
 
 impl Assignments {
     /// Returns `true` if the first assignment fully contains the second assignment, or vice versa.
     /// Returns `false` otherwise.
     fn fully_overlapping(&self) -> bool {
-        self.first.fully_contains(&self.second) || self.second.fully_contains(&self.first)
+        self.first.contains_interval(&self.second) || self.second.contains_interval(&self.first)
     }
 
     /// Returns `true` if the first assignment overlaps the second assignment. Returns `false`
@@ -57,32 +36,51 @@ impl Assignments {
 }
 
 impl FromStr for Assignments {
-    type Err = Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (left, right) = s
-            .split_once(",")
-            .ok_or_else(|| anyhow!("Invalid input: not enough assignments"))?;
-
-        Ok(Self {
-            first: Assignment::from_str(left)?,
-            second: Assignment::from_str(right)?,
-        })
+        let assignments = pair(terminated(assignment(), literal(",")), assignment());
+        let (first, second) = assignments.parse_line(s)?;
+
+        Ok(Self { first, second })
     }
 }
 
 pub fn parse_input(lines: &[String]) -> Result<Vec<Assignments>> {
-    lines.iter().map(|l| Assignments::from_str(l)).collect()
+    Ok(utils::parse_lines(lines)?)
 }
 
-pub fn part_one(parsed: &Vec<Assignments>) -> usize {
+pub fn part_one(parsed: &[Assignments]) -> usize {
     parsed.iter().filter(|&a| a.fully_overlapping()).count()
 }
 
-pub fn part_two(parsed: &Vec<Assignments>) -> usize {
+pub fn part_two(parsed: &[Assignments]) -> usize {
     parsed.iter().filter(|&a| a.overlapping()).count()
 }
 
+pub struct Day04;
+
+impl Day for Day04 {
+    const DAY: u8 = 4;
+    const TITLE: &'static str = "camp cleanup";
+
+    type Parsed = Vec<Assignments>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(lines: &[String]) -> Result<Self::Parsed> {
+        parse_input(lines)
+    }
+
+    fn part_one(parsed: &Self::Parsed) -> Self::PartOne {
+        part_one(parsed)
+    }
+
+    fn part_two(parsed: &Self::Parsed) -> Self::PartTwo {
+        part_two(parsed)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,7 +1,14 @@
-use anyhow::{anyhow, Error, Result};
+use anyhow::Result;
 use std::fmt;
 use std::str::FromStr;
 
+use crate::parser::{
+    self, any_char, delimited, integer, literal, map, or, pair, preceded, separated_list, try_map,
+    Parser,
+};
+use crate::solution::Day;
+use crate::utils::{self, ParseError};
+
 #[derive(Debug, Clone, Default, Eq, PartialEq)]
 struct SupplyStack {
     stack: Vec<char>,
@@ -15,41 +22,29 @@ struct Procedure {
 }
 
 impl FromStr for Procedure {
-    type Err = Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut tokens = s.split(" ");
-
-        tokens
-            .next()
-            .ok_or_else(|| anyhow!("Invalid procedure input: {}", s))?;
-        let quantity = tokens
-            .next()
-            .and_then(|t| t.parse::<usize>().ok())
-            .ok_or_else(|| anyhow!("Could not parse `quantity` from input: {}", s))?;
-
-        tokens
-            .next()
-            .ok_or_else(|| anyhow!("Invalid procedure input: {}", s))?;
-        let from = tokens
-            .next()
-            .and_then(|t| t.parse::<usize>().ok())
-            .ok_or_else(|| anyhow!("Could not parse `from` from input: {}", s))?;
-
-        tokens
-            .next()
-            .ok_or_else(|| anyhow!("Invalid procedure input: {}", s))?;
-        let to = tokens
-            .next()
-            .and_then(|t| t.parse::<usize>().ok())
-            .ok_or_else(|| anyhow!("Could not parse `to` from input: {}", s))?;
-
-        // we use 0 index
-        Ok(Self {
-            from: from - 1,
-            to: to - 1,
-            quantity,
-        })
+        // stack numbers start from 1, but we use 0 index
+        let stack = || {
+            try_map(integer::<usize>(), |n| {
+                n.checked_sub(1)
+                    .ok_or_else(|| ParseError::new("a stack number starting from 1"))
+            })
+        };
+
+        // it looks like:
+        //   move 1 from 2 to 1
+        let procedure = pair(
+            preceded(literal("move "), integer()),
+            pair(
+                preceded(literal(" from "), stack()),
+                preceded(literal(" to "), stack()),
+            ),
+        );
+        let (quantity, (from, to)) = procedure.parse_line(s)?;
+
+        Ok(Self { from, to, quantity })
     }
 }
 
@@ -61,7 +56,7 @@ enum Mover {
 
 impl Mover {
     /// Applies the provided [procedure] to the [supply_stacks]
-    fn apply_procedure(&self, procedure: &Procedure, supply_stacks: &mut Vec<SupplyStack>) {
+    fn apply_procedure(&self, procedure: &Procedure, supply_stacks: &mut [SupplyStack]) {
         match self {
             Self::CrateMover9000 => {
                 for _ in 0..procedure.quantity {
@@ -83,7 +78,7 @@ impl Mover {
                             let len = s.stack.len();
                             s.stack.get(len - procedure.quantity + i)
                         })
-                        .map(|l| *l);
+                        .copied();
                     if let Some(l) = label {
                         if let Some(s) = supply_stacks.get_mut(procedure.to) {
                             s.stack.push(l);
@@ -131,26 +126,48 @@ impl fmt::Display for Ship {
 }
 
 pub fn parse_input(lines: &[String]) -> Result<Ship> {
-    let mut iter = lines.iter().rev();
+    let split = lines.iter().position(|l| l.is_empty()).ok_or_else(|| {
+        ParseError::new("an empty line between the stacks and the procedures").at_line(lines.len())
+    })?;
+    let (drawing, procedures) = lines.split_at(split);
 
     // procedures are in reverse order
-    let mut procedures = Vec::default();
-    while let Some(l) = iter.next() {
-        if l == "" {
-            break;
-        }
-
-        procedures.push(Procedure::from_str(l)?);
-    }
-
-    let l = iter.next().ok_or_else(|| anyhow!("Invalid input"))?;
-    let count = l.split_whitespace().count();
+    let mut procedures: Vec<Procedure> =
+        utils::parse_lines(&procedures[1..]).map_err(|e| e.at_line(split + 1))?;
+    procedures.reverse();
+
+    let (labels, rows) = drawing
+        .split_last()
+        .ok_or_else(|| ParseError::new("a line of stack labels").at_line(0))?;
+
+    // the labels look like ` 1   2   3 `, and each slot above them is either a crate like `[Z]`
+    // or three spaces
+    let count = separated_list(
+        delimited(literal(" "), integer::<usize>(), literal(" ")),
+        literal(" "),
+    )
+    .parse_line(labels)
+    .map_err(|e| e.at_line(rows.len()))?
+    .len();
+    let slot = or(
+        map(delimited(literal("["), any_char(), literal("]")), Some),
+        map(literal("   "), |_| None),
+    );
+    let rows = parser::each_line(rows, separated_list(slot, literal(" ")))?;
 
     let mut supply_stacks = vec![SupplyStack::default(); count];
-    for l in iter {
-        for (i, label) in l.chars().skip(1).step_by(4).enumerate() {
-            if !label.is_whitespace() {
-                supply_stacks[i].stack.push(label);
+    for (row, slots) in rows.iter().enumerate().rev() {
+        for (i, label) in slots.iter().enumerate() {
+            if let Some(label) = label {
+                supply_stacks
+                    .get_mut(i)
+                    .ok_or_else(|| {
+                        ParseError::new(format!("at most {} stacks", count))
+                            .at_line(row)
+                            .at_offset(i * 4)
+                    })?
+                    .stack
+                    .push(*label);
             }
         }
     }
@@ -161,14 +178,39 @@ pub fn parse_input(lines: &[String]) -> Result<Ship> {
     })
 }
 
-pub fn part_one(parsed: &mut Ship) -> String {
-    parsed.apply_procedures_with(Mover::CrateMover9000);
-    parsed.to_string()
+pub fn part_one(parsed: &Ship) -> String {
+    let mut ship = parsed.clone();
+    ship.apply_procedures_with(Mover::CrateMover9000);
+    ship.to_string()
+}
+
+pub fn part_two(parsed: &Ship) -> String {
+    let mut ship = parsed.clone();
+    ship.apply_procedures_with(Mover::CrateMover9001);
+    ship.to_string()
 }
 
-pub fn part_two(parsed: &mut Ship) -> String {
-    parsed.apply_procedures_with(Mover::CrateMover9001);
-    parsed.to_string()
+pub struct Day05;
+
+impl Day for Day05 {
+    const DAY: u8 = 5;
+    const TITLE: &'static str = "supply stacks";
+
+    type Parsed = Ship;
+    type PartOne = String;
+    type PartTwo = String;
+
+    fn parse(lines: &[String]) -> Result<Self::Parsed> {
+        parse_input(lines)
+    }
+
+    fn part_one(parsed: &Self::Parsed) -> Self::PartOne {
+        part_one(parsed)
+    }
+
+    fn part_two(parsed: &Self::Parsed) -> Self::PartTwo {
+        part_two(parsed)
+    }
 }
 
 #[cfg(test)]
@@ -179,14 +221,14 @@ mod tests {
     #[test]
     fn part_one_test() {
         let lines = utils::load_input("inputs/day_05.example").expect("could not load input");
-        let mut parsed = parse_input(&lines).expect("could not parse input");
-        assert_eq!(part_one(&mut parsed), "CMZ");
+        let parsed = parse_input(&lines).expect("could not parse input");
+        assert_eq!(part_one(&parsed), "CMZ");
     }
 
     #[test]
     fn part_two_test() {
         let lines = utils::load_input("inputs/day_05.example").expect("could not load input");
-        let mut parsed = parse_input(&lines).expect("could not parse input");
-        assert_eq!(part_two(&mut parsed), "MCD");
+        let parsed = parse_input(&lines).expect("could not parse input");
+        assert_eq!(part_two(&parsed), "MCD");
     }
 }
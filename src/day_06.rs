@@ -1,9 +1,12 @@
-use anyhow::{bail, Result};
+use anyhow::Result;
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
-pub struct Signal<'a>(&'a str);
+use crate::solution::Day;
+use crate::utils::ParseError;
 
-impl Signal<'_> {
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Signal(String);
+
+impl Signal {
     fn start_of_marker(&self, unique_window_size: usize) -> usize {
         let mut last_index = [self.0.len(); 26];
         let mut start = 0;
@@ -28,10 +31,19 @@ impl Signal<'_> {
 
 pub fn parse_input(lines: &[String]) -> Result<Signal> {
     if lines.len() != 1 {
-        bail!("Input should be exactly 1 line");
+        return Err(ParseError::new("exactly 1 line")
+            .at_line(lines.len().min(1))
+            .into());
+    }
+
+    if let Some(i) = lines[0].find(|c: char| !c.is_ascii_lowercase()) {
+        return Err(ParseError::new("a lowercase letter")
+            .at_line(0)
+            .at_offset(i)
+            .into());
     }
 
-    Ok(Signal(&lines[0]))
+    Ok(Signal(lines[0].to_owned()))
 }
 
 pub fn part_one(parsed: &Signal) -> usize {
@@ -42,6 +54,29 @@ pub fn part_two(parsed: &Signal) -> usize {
     parsed.start_of_marker(14)
 }
 
+pub struct Day06;
+
+impl Day for Day06 {
+    const DAY: u8 = 6;
+    const TITLE: &'static str = "tuning trouble";
+
+    type Parsed = Signal;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(lines: &[String]) -> Result<Self::Parsed> {
+        parse_input(lines)
+    }
+
+    fn part_one(parsed: &Self::Parsed) -> Self::PartOne {
+        part_one(parsed)
+    }
+
+    fn part_two(parsed: &Self::Parsed) -> Self::PartTwo {
+        part_two(parsed)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -50,37 +85,43 @@ mod tests {
     #[test]
     fn start_of_marker_test() {
         assert_eq!(
-            Signal("mjqjpqmgbljsphdztnvjfqwrcgsmlb").start_of_marker(4),
+            Signal("mjqjpqmgbljsphdztnvjfqwrcgsmlb".to_owned()).start_of_marker(4),
             7
         );
-        assert_eq!(Signal("bvwbjplbgvbhsrlpgdmjqwftvncz").start_of_marker(4), 5);
-        assert_eq!(Signal("nppdvjthqldpwncqszvftbrmjlhg").start_of_marker(4), 6);
         assert_eq!(
-            Signal("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg").start_of_marker(4),
+            Signal("bvwbjplbgvbhsrlpgdmjqwftvncz".to_owned()).start_of_marker(4),
+            5
+        );
+        assert_eq!(
+            Signal("nppdvjthqldpwncqszvftbrmjlhg".to_owned()).start_of_marker(4),
+            6
+        );
+        assert_eq!(
+            Signal("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg".to_owned()).start_of_marker(4),
             10
         );
         assert_eq!(
-            Signal("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw").start_of_marker(4),
+            Signal("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw".to_owned()).start_of_marker(4),
             11
         );
         assert_eq!(
-            Signal("mjqjpqmgbljsphdztnvjfqwrcgsmlb").start_of_marker(14),
+            Signal("mjqjpqmgbljsphdztnvjfqwrcgsmlb".to_owned()).start_of_marker(14),
             19
         );
         assert_eq!(
-            Signal("bvwbjplbgvbhsrlpgdmjqwftvncz").start_of_marker(14),
+            Signal("bvwbjplbgvbhsrlpgdmjqwftvncz".to_owned()).start_of_marker(14),
             23
         );
         assert_eq!(
-            Signal("nppdvjthqldpwncqszvftbrmjlhg").start_of_marker(14),
+            Signal("nppdvjthqldpwncqszvftbrmjlhg".to_owned()).start_of_marker(14),
             23
         );
         assert_eq!(
-            Signal("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg").start_of_marker(14),
+            Signal("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg".to_owned()).start_of_marker(14),
             29
         );
         assert_eq!(
-            Signal("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw").start_of_marker(14),
+            Signal("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw".to_owned()).start_of_marker(14),
             26
         );
     }
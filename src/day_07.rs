@@ -1,7 +1,11 @@
-use anyhow::{anyhow, Error, Result};
+use anyhow::Result;
 use rustc_hash::FxHashMap;
 use std::{cell::RefCell, rc::Rc, str::FromStr};
 
+use crate::parser::{integer, literal, map, or, pair, preceded, terminated, word, Parser};
+use crate::solution::Day;
+use crate::utils::{self, ParseError};
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum OutputLine {
     Cd(String),
@@ -11,33 +15,22 @@ pub enum OutputLine {
 }
 
 impl FromStr for OutputLine {
-    type Err = Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let tokens = s.split_whitespace();
-        let ret = if s.starts_with("$ cd") {
-            Self::Cd(
-                tokens
-                    .skip(2)
-                    .next()
-                    .ok_or_else(|| anyhow!("Invalid cd output line"))?
-                    .to_owned(),
-            )
-        } else if s.starts_with("$ ls") {
-            Self::Ls
-        } else if s.starts_with("dir") {
-            Self::Dir(
-                tokens
-                    .skip(1)
-                    .next()
-                    .ok_or_else(|| anyhow!("Invalid dir output line"))?
-                    .to_owned(),
-            )
-        } else {
-            Self::File(File::from_str(s)?)
-        };
+        let name = || map(word(), str::to_owned);
+        let output_line = or(
+            or(
+                map(preceded(literal("$ cd "), name()), Self::Cd),
+                map(literal("$ ls"), |_| Self::Ls),
+            ),
+            or(
+                map(preceded(literal("dir "), name()), Self::Dir),
+                map(file(), Self::File),
+            ),
+        );
 
-        Ok(ret)
+        output_line.parse_line(s)
     }
 }
 
@@ -46,17 +39,18 @@ pub struct File {
     size: usize,
 }
 
+/// Parses a file like `14848514 b.txt`
+fn file<'a>() -> impl Parser<'a, File> {
+    map(terminated(integer(), pair(literal(" "), word())), |size| {
+        File { size }
+    })
+}
+
 impl FromStr for File {
-    type Err = Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Self {
-            size: s
-                .split_whitespace()
-                .next()
-                .ok_or_else(|| anyhow!("Invalid file output line"))?
-                .parse::<usize>()?,
-        })
+        file().parse_line(s)
     }
 }
 
@@ -69,13 +63,12 @@ pub struct Dir {
 }
 
 impl Dir {
-    fn build(lines: &[String]) -> Result<DirWrapper> {
+    fn build(lines: &[String]) -> Result<DirWrapper, ParseError> {
         let root = Rc::new(RefCell::new(Dir::default()));
         let mut cur = Rc::clone(&root);
         let mut stack = vec![];
 
-        for line in lines {
-            let output_line = OutputLine::from_str(line)?;
+        for output_line in utils::parse_lines(lines)? {
             match output_line {
                 OutputLine::Cd(name) if name == "/" => {
                     cur = Rc::clone(&root);
@@ -114,8 +107,8 @@ impl Dir {
     fn calculate_size(&mut self) -> usize {
         let result: usize = self
             .dirs
-            .iter()
-            .map(|(_, v)| v.borrow_mut().calculate_size())
+            .values()
+            .map(|v| v.borrow_mut().calculate_size())
             .sum();
 
         self.size += result;
@@ -127,16 +120,16 @@ impl Dir {
 
         sum + self
             .dirs
-            .iter()
-            .map(|(_, v)| v.borrow().sum_dir_sizes(max_size))
+            .values()
+            .map(|v| v.borrow().sum_dir_sizes(max_size))
             .sum::<usize>()
     }
 
     fn min_freeable_size(&self, desired: usize) -> usize {
         let result = self
             .dirs
-            .iter()
-            .map(|(_, v)| v.borrow().min_freeable_size(desired))
+            .values()
+            .map(|v| v.borrow().min_freeable_size(desired))
             .filter(|&s| s >= desired)
             .min()
             .unwrap_or(0);
@@ -166,6 +159,29 @@ pub fn part_two(parsed: &DirWrapper) -> usize {
     parsed.borrow().min_freeable_size(desired)
 }
 
+pub struct Day07;
+
+impl Day for Day07 {
+    const DAY: u8 = 7;
+    const TITLE: &'static str = "no space left on device";
+
+    type Parsed = DirWrapper;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(lines: &[String]) -> Result<Self::Parsed> {
+        parse_input(lines)
+    }
+
+    fn part_one(parsed: &Self::Parsed) -> Self::PartOne {
+        part_one(parsed)
+    }
+
+    fn part_two(parsed: &Self::Parsed) -> Self::PartTwo {
+        part_two(parsed)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
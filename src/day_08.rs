@@ -1,201 +1,122 @@
 use anyhow::Result;
 
-use crate::grid::Grid;
+use crate::grid::{Digit, Glyph, Grid};
+use crate::solution::Day;
+use crate::utils::{Input, ParseError};
+
+/// Returns, for each tree along [line], whether it can be seen from the start of the line
+fn visible_from_start<'a>(line: impl Iterator<Item = &'a Digit>) -> Vec<bool> {
+    let mut tallest = None;
+
+    line.map(|&h| {
+        let visible = tallest.is_none_or(|t| h > t);
+        if visible {
+            tallest = Some(h);
+        }
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
-enum Visibility {
-    Unknown,
-    Visible,
+        visible
+    })
+    .collect()
 }
 
-#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
-struct ViewingDistances {
-    left: usize,
-    right: usize,
-    up: usize,
-    down: usize,
-}
+/// Returns, for each tree along [line], how many trees it can see looking back towards the start
+/// of the line
+fn viewing_distances<'a>(line: impl Iterator<Item = &'a Digit>) -> Vec<usize> {
+    // the trees that are not yet hidden behind a taller one, as (position, height)
+    let mut stack: Vec<(usize, Digit)> = Vec::default();
 
-impl ViewingDistances {
-    fn scenic_score(&self) -> usize {
-        self.left * self.right * self.up * self.down
-    }
+    line.enumerate()
+        .map(|(k, &h)| {
+            while stack.last().is_some_and(|&(_, t)| t < h) {
+                stack.pop();
+            }
+
+            let distance = k - stack.last().map_or(0, |&(p, _)| p);
+            stack.push((k, h));
+            distance
+        })
+        .collect()
 }
 
-type Grove = Grid<u8>;
+type Grove = Grid<Digit>;
 
 impl Grove {
-    fn trees_visible(&self) -> usize {
-        if self.n < 2 || self.m < 2 {
-            return self.n * self.m;
-        }
-
-        // now we know its at least 2x2
-        let mut count = 0;
-        let mut vis_grid = Grid::new(self.n, self.m, Visibility::Unknown);
-
-        let mut top_down_maxes = vec![0; self.m];
-        let mut bottom_up_maxes = vec![0; self.m];
-
-        for i in 0..self.n {
-            let mut left_max = 0;
-            let mut right_max = 0;
+    /// Combines [f] applied along every row and column, in both directions, into a grid
+    fn sweep<U: Copy>(
+        &self,
+        init: U,
+        f: impl Fn(&mut dyn Iterator<Item = &Digit>) -> Vec<U>,
+        combine: impl Fn(U, U) -> U,
+    ) -> Grid<U> {
+        let mut acc = self.map(|_| init);
+
+        for (i, row) in self.rows().enumerate() {
+            let forward = f(&mut row.iter());
+            let backward = f(&mut row.iter().rev());
 
             for j in 0..self.m {
-                let left_coord = (i, j).into();
-                let left = self[left_coord];
-
-                let right_coord = (i, self.m - j - 1).into();
-                let right = self[right_coord];
-
-                let bottom_coord = (self.n - i - 1, j).into();
-                let bottom = self[bottom_coord];
-
-                if vis_grid[left_coord] != Visibility::Visible
-                    && (self.is_on_edge(left_coord) || left > left_max || left > top_down_maxes[j])
-                {
-                    vis_grid[left_coord] = Visibility::Visible;
-                    count += 1;
-                }
-
-                if vis_grid[right_coord] != Visibility::Visible
-                    && (self.is_on_edge(right_coord) || right > right_max)
-                {
-                    vis_grid[right_coord] = Visibility::Visible;
-                    count += 1;
-                }
-
-                if vis_grid[bottom_coord] != Visibility::Visible
-                    && (self.is_on_edge(bottom_coord) || bottom > bottom_up_maxes[j])
-                {
-                    vis_grid[bottom_coord] = Visibility::Visible;
-                    count += 1;
-                }
-
-                if left > left_max {
-                    left_max = left;
-                }
-
-                if right > right_max {
-                    right_max = right;
-                }
-
-                if left > top_down_maxes[j] {
-                    top_down_maxes[j] = left;
-                }
-
-                if bottom > bottom_up_maxes[j] {
-                    bottom_up_maxes[j] = bottom;
-                }
+                let coord = (i, j).into();
+                acc[coord] = combine(acc[coord], combine(forward[j], backward[self.m - j - 1]));
             }
         }
 
-        count
-    }
-
-    fn max_scenic_score(&self) -> usize {
-        let mut vd_grid = Grid::new(self.n, self.m, ViewingDistances::default());
-
-        // left to right
-        for i in 0..self.n {
-            let mut stack = Vec::with_capacity(self.m);
+        for j in 0..self.m {
+            let forward = f(&mut self.col(j));
+            let backward = f(&mut self.col(j).rev());
 
-            for j in 0..self.m {
+            for i in 0..self.n {
                 let coord = (i, j).into();
-                let h = self[coord];
-
-                while let Some(&k) = stack.last() {
-                    if self[(i, k).into()] < h {
-                        stack.pop();
-                    } else {
-                        break;
-                    }
-                }
-                vd_grid[coord].left = j - stack.last().map(|k| *k).unwrap_or(0);
-                stack.push(j);
+                acc[coord] = combine(acc[coord], combine(forward[i], backward[self.n - i - 1]));
             }
         }
 
-        // right to left
-        for i in 0..self.n {
-            let mut stack = Vec::with_capacity(self.m);
+        acc
+    }
 
-            for j in 0..self.m {
-                let jj = self.m - j - 1;
-                let coord = (i, jj).into();
-                let h = self[coord];
-
-                while let Some(&k) = stack.last() {
-                    let kk = self.m - k - 1;
-                    if self[(i, kk).into()] < h {
-                        stack.pop();
-                    } else {
-                        break;
-                    }
-                }
-                vd_grid[coord].right = j - stack.last().map(|k| *k).unwrap_or(0);
-                stack.push(j);
-            }
-        }
+    fn trees_visible(&self) -> usize {
+        let visible = self.sweep(false, |line| visible_from_start(line), |a, b| a || b);
+        visible.find_all(|&v| v).count()
+    }
 
-        // top to bottom
-        for j in 0..self.m {
-            let mut stack = Vec::with_capacity(self.n);
+    fn max_scenic_score(&self) -> usize {
+        let scores = self.sweep(1, |line| viewing_distances(line), |a, b| a * b);
+        scores.cells().map(|(_, &s)| s).max().unwrap_or(0)
+    }
+}
 
-            for i in 0..self.n {
-                let coord = (i, j).into();
-                let h = self[coord];
-
-                while let Some(&k) = stack.last() {
-                    if self[(k, j).into()] < h {
-                        stack.pop();
-                    } else {
-                        break;
-                    }
-                }
-                vd_grid[coord].up = i - stack.last().map(|k| *k).unwrap_or(0);
-                stack.push(i);
-            }
-        }
+pub fn parse_input(lines: &[String]) -> Result<Grove> {
+    Ok(Grove::try_from(lines)?)
+}
 
-        // bottom to top
-        for j in 0..self.m {
-            let mut stack = Vec::with_capacity(self.n);
+/// Parses the grove one line at a time, without allocating a string for each line
+pub fn parse_reader(mut input: Input) -> Result<Grove> {
+    let mut heights = Vec::default();
+    let mut width = 0;
+    let mut n = 0;
 
-            for i in 0..self.n {
-                let ii = self.n - i - 1;
-                let coord = (ii, j).into();
-                let h = self[coord];
-
-                while let Some(&k) = stack.last() {
-                    let kk = self.n - k - 1;
-                    if self[(kk, j).into()] < h {
-                        stack.pop();
-                    } else {
-                        break;
-                    }
-                }
-                vd_grid[coord].down = i - stack.last().map(|k| *k).unwrap_or(0);
-                stack.push(i);
-            }
+    while let Some(line) = input.next_line()? {
+        // each height is parsed the same way as in a [Grid] of [Digit]
+        for (j, &b) in line.iter().enumerate() {
+            heights.push(Digit::from_glyph(b as char).map_err(|e| e.at_line(n).at_offset(j))?);
         }
 
-        let mut max = 0;
-        for i in 0..self.n {
-            for j in 0..self.m {
-                let score = vd_grid[(i, j).into()].scenic_score();
-                if score > max {
-                    max = score;
-                }
-            }
+        if n == 0 {
+            width = line.len();
+        } else if line.len() != width {
+            return Err(ParseError::new(format!("a row of {} trees", width))
+                .at_line(n)
+                .at_offset(line.len().min(width))
+                .into());
         }
 
-        max
+        n += 1;
     }
-}
 
-pub fn parse_input(lines: &[String]) -> Result<Grove> {
-    Ok(Grove::try_from(lines)?)
+    if width == 0 {
+        return Err(ParseError::new("a non-empty grove").at_line(0).into());
+    }
+
+    Ok(Grid::from_cells(n, width, heights))
 }
 
 pub fn part_one(parsed: &Grove) -> usize {
@@ -206,6 +127,33 @@ pub fn part_two(parsed: &Grove) -> usize {
     parsed.max_scenic_score()
 }
 
+pub struct Day08;
+
+impl Day for Day08 {
+    const DAY: u8 = 8;
+    const TITLE: &'static str = "treetop tree house";
+
+    type Parsed = Grove;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(lines: &[String]) -> Result<Self::Parsed> {
+        parse_input(lines)
+    }
+
+    fn parse_reader(input: Input) -> Result<Self::Parsed> {
+        parse_reader(input)
+    }
+
+    fn part_one(parsed: &Self::Parsed) -> Self::PartOne {
+        part_one(parsed)
+    }
+
+    fn part_two(parsed: &Self::Parsed) -> Self::PartTwo {
+        part_two(parsed)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -224,4 +172,12 @@ mod tests {
         let parsed = parse_input(&lines).expect("could not parse input");
         assert_eq!(part_two(&parsed), 8);
     }
+
+    #[test]
+    fn parse_reader_test() {
+        let input = Input::open("inputs/day_08.example").expect("could not open input");
+        let parsed = parse_reader(input).expect("could not parse input");
+        assert_eq!(part_one(&parsed), 21);
+        assert_eq!(part_two(&parsed), 8);
+    }
 }
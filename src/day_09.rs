@@ -1,49 +1,29 @@
-use anyhow::{anyhow, bail, Error, Result};
+use anyhow::Result;
 use rustc_hash::FxHashSet;
 use std::str::FromStr;
 
-use crate::grid::Coordinate;
-
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-impl FromStr for Direction {
-    type Err = Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(match s {
-            "U" => Self::Up,
-            "D" => Self::Down,
-            "L" => Self::Left,
-            "R" => Self::Right,
-            _ => bail!("Invalid direction: {}", s),
-        })
-    }
-}
+use crate::grid::{Coordinate, Direction4};
+use crate::parser::{integer, literal, pair, terminated, try_map, word, Parser};
+use crate::solution::Day;
+use crate::utils::{self, ParseError};
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub struct Motion {
-    dir: Direction,
+    dir: Direction4,
     len: usize,
 }
 
 impl FromStr for Motion {
-    type Err = Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (a, b) = s
-            .split_once(" ")
-            .ok_or_else(|| anyhow!("Invalid motion: {}", s))?;
-
-        Ok(Self {
-            dir: Direction::from_str(a)?,
-            len: b.parse()?,
-        })
+        let motion = pair(
+            terminated(try_map(word(), Direction4::from_str), literal(" ")),
+            integer(),
+        );
+        let (dir, len) = motion.parse_line(s)?;
+
+        Ok(Self { dir, len })
     }
 }
 
@@ -69,18 +49,13 @@ impl State {
 
         for motion in motions {
             for _ in 0..motion.len {
-                self.simulate_step(&motion.dir)
+                self.simulate_step(motion.dir)
             }
         }
     }
 
-    fn simulate_step(&mut self, dir: &Direction) {
-        self.rope[0] = match dir {
-            Direction::Up => self.rope[0].north(),
-            Direction::Down => self.rope[0].south(),
-            Direction::Left => self.rope[0].west(),
-            Direction::Right => self.rope[0].east(),
-        };
+    fn simulate_step(&mut self, dir: Direction4) {
+        self.rope[0] = self.rope[0].step(dir, 1);
 
         for i in 1..self.rope.len() {
             let cur = self.rope[i];
@@ -115,21 +90,44 @@ impl State {
 }
 
 pub fn parse_input(lines: &[String]) -> Result<Vec<Motion>> {
-    lines.iter().map(|l| Motion::from_str(l)).collect()
+    Ok(utils::parse_lines(lines)?)
 }
 
-pub fn part_one(parsed: &Vec<Motion>) -> usize {
+pub fn part_one(parsed: &[Motion]) -> usize {
     let mut state = State::new(2);
     state.simulate(parsed);
     state.visited_positions()
 }
 
-pub fn part_two(parsed: &Vec<Motion>) -> usize {
+pub fn part_two(parsed: &[Motion]) -> usize {
     let mut state = State::new(10);
     state.simulate(parsed);
     state.visited_positions()
 }
 
+pub struct Day09;
+
+impl Day for Day09 {
+    const DAY: u8 = 9;
+    const TITLE: &'static str = "rope bridge";
+
+    type Parsed = Vec<Motion>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(lines: &[String]) -> Result<Self::Parsed> {
+        parse_input(lines)
+    }
+
+    fn part_one(parsed: &Self::Parsed) -> Self::PartOne {
+        part_one(parsed)
+    }
+
+    fn part_two(parsed: &Self::Parsed) -> Self::PartTwo {
+        part_two(parsed)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
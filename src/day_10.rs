@@ -1,6 +1,10 @@
-use anyhow::{anyhow, bail, Error, Result};
+use anyhow::Result;
 use std::str::FromStr;
 
+use crate::parser::{integer, literal, map, or, preceded, Parser};
+use crate::solution::Day;
+use crate::utils::{self, ParseError};
+
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 enum Instruction {
     Noop,
@@ -8,21 +12,15 @@ enum Instruction {
 }
 
 impl FromStr for Instruction {
-    type Err = Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s == "noop" {
-            return Ok(Self::Noop);
-        }
+        let instruction = or(
+            map(literal("noop"), |_| Self::Noop),
+            map(preceded(literal("addx "), integer()), Self::Addx),
+        );
 
-        let (a, b) = s
-            .split_once(" ")
-            .ok_or_else(|| anyhow!("Could not parse instruction: {}", s))?;
-
-        match a {
-            "addx" => Ok(Self::Addx(b.parse()?)),
-            _ => bail!("Could not parse instruction: {}", s),
-        }
+        instruction.parse_line(s)
     }
 }
 
@@ -108,8 +106,7 @@ pub fn parse_input(lines: &[String]) -> Result<(Cpu, Crt)> {
     let mut cpu = Cpu::default();
     let mut crt = Crt::default();
 
-    for line in lines {
-        let instruction = Instruction::from_str(line)?;
+    for instruction in utils::parse_lines::<Instruction>(lines)? {
         crt.draw(&cpu, &instruction);
         cpu.run(&instruction)
     }
@@ -121,8 +118,31 @@ pub fn part_one(parsed: &(Cpu, Crt)) -> isize {
     parsed.0.signal_strength
 }
 
-pub fn part_two(parsed: &(Cpu, Crt)) -> &str {
-    &parsed.1.output
+pub fn part_two(parsed: &(Cpu, Crt)) -> String {
+    parsed.1.output.clone()
+}
+
+pub struct Day10;
+
+impl Day for Day10 {
+    const DAY: u8 = 10;
+    const TITLE: &'static str = "cathode-ray tube";
+
+    type Parsed = (Cpu, Crt);
+    type PartOne = isize;
+    type PartTwo = String;
+
+    fn parse(lines: &[String]) -> Result<Self::Parsed> {
+        parse_input(lines)
+    }
+
+    fn part_one(parsed: &Self::Parsed) -> Self::PartOne {
+        part_one(parsed)
+    }
+
+    fn part_two(parsed: &Self::Parsed) -> Self::PartTwo {
+        part_two(parsed)
+    }
 }
 
 #[cfg(test)]
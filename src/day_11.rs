@@ -1,7 +1,11 @@
 use std::collections::VecDeque;
 use std::str::FromStr;
 
-use anyhow::{anyhow, bail, Error, Result};
+use anyhow::Result;
+
+use crate::parser::{self, delimited, integer, literal, map, or, preceded, separated_list, Parser};
+use crate::solution::Day;
+use crate::utils::{self, ParseError};
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 enum Operation {
@@ -11,23 +15,26 @@ enum Operation {
 }
 
 impl FromStr for Operation {
-    type Err = Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         // it looks like:
         //   Operation: new = old + 6
-        let tokens = s[23..]
-            .split_once(' ')
-            .ok_or_else(|| anyhow!("Could not parse operation: {}", s))?;
-
-        let ret = match tokens {
-            (_, "old") => Self::Square,
-            ("+", value) => Self::Add(value.parse()?),
-            ("*", value) => Self::Multiply(value.parse()?),
-            _ => bail!("Could not parse operation: {}", s),
-        };
+        let operation = preceded(
+            literal("  Operation: new = old "),
+            or(
+                map(preceded(literal("+ "), integer()), Self::Add),
+                preceded(
+                    literal("* "),
+                    or(
+                        map(literal("old"), |_| Self::Square),
+                        map(integer(), Self::Multiply),
+                    ),
+                ),
+            ),
+        );
 
-        Ok(ret)
+        operation.parse_line(s)
     }
 }
 
@@ -49,7 +56,7 @@ struct Test {
 }
 
 impl TryFrom<&[String]> for Test {
-    type Error = Error;
+    type Error = ParseError;
 
     fn try_from(lines: &[String]) -> Result<Self, Self::Error> {
         // it looks like:
@@ -57,13 +64,20 @@ impl TryFrom<&[String]> for Test {
         //     If true: throw to monkey 2
         //     If false: throw to monkey 0
         if lines.len() != 3 {
-            bail!("Test should have 3 lines")
+            return Err(ParseError::new("a test of 3 lines").at_line(0));
         }
 
+        let target = |i: usize, prefix: &'static str| {
+            preceded(literal(prefix), integer())
+                .parse_line(&lines[i])
+                .map_err(|e| e.at_line(i))
+        };
+
         Ok(Self {
-            divisible_by: lines[0][21..].parse()?,
-            true_target: lines[1][29..].parse()?,
-            false_target: lines[2][30..].parse()?,
+            divisible_by: preceded(literal("  Test: divisible by "), integer())
+                .parse_line(&lines[0])?,
+            true_target: target(1, "    If true: throw to monkey ")?,
+            false_target: target(2, "    If false: throw to monkey ")?,
         })
     }
 }
@@ -71,7 +85,7 @@ impl TryFrom<&[String]> for Test {
 impl Test {
     /// Performs the test on the given [value] and returns the intended target
     fn apply_to(&self, value: u64) -> usize {
-        if value % self.divisible_by == 0 {
+        if value.is_multiple_of(self.divisible_by) {
             self.true_target
         } else {
             self.false_target
@@ -88,7 +102,7 @@ pub struct Monkey {
 }
 
 impl TryFrom<&[String]> for Monkey {
-    type Error = Error;
+    type Error = ParseError;
 
     fn try_from(lines: &[String]) -> Result<Self, Self::Error> {
         // it looks like:
@@ -99,28 +113,34 @@ impl TryFrom<&[String]> for Monkey {
         //     If true: throw to monkey 2
         //     If false: throw to monkey 3
         if lines.len() != 6 {
-            bail!("Monkey should have 6 lines")
+            return Err(ParseError::new("a monkey of 6 lines").at_line(0));
         }
 
+        delimited(literal("Monkey "), integer::<usize>(), literal(":")).parse_line(&lines[0])?;
+
+        let items = preceded(
+            literal("  Starting items: "),
+            separated_list(integer(), literal(", ")),
+        )
+        .parse_line(&lines[1])
+        .map_err(|e| e.at_line(1))?;
+
         Ok(Self {
-            items: lines[1][18..]
-                .split(", ")
-                .map(|t| t.parse::<u64>())
-                .collect::<Result<VecDeque<_>, _>>()?,
-            operation: Operation::from_str(&lines[2])?,
-            test: lines[3..6].try_into()?,
+            items: items.into(),
+            operation: Operation::from_str(&lines[2]).map_err(|e| e.at_line(2))?,
+            test: Test::try_from(&lines[3..6]).map_err(|e| e.at_line(3))?,
             num_inspections: 0,
         })
     }
 }
 
-fn simulate(monkeys: &mut Vec<Monkey>, rounds: usize, reducer: impl Fn(u64) -> u64) {
+fn simulate(monkeys: &mut [Monkey], rounds: usize, reducer: impl Fn(u64) -> u64) {
     for _ in 0..rounds {
         simulate_one_round(monkeys, &reducer);
     }
 }
 
-fn simulate_one_round(monkeys: &mut Vec<Monkey>, reducer: impl Fn(u64) -> u64) {
+fn simulate_one_round(monkeys: &mut [Monkey], reducer: impl Fn(u64) -> u64) {
     for i in 0..monkeys.len() {
         monkeys[i].num_inspections += monkeys[i].items.len();
         while let Some(cur_item) = monkeys[i].items.pop_front() {
@@ -132,22 +152,42 @@ fn simulate_one_round(monkeys: &mut Vec<Monkey>, reducer: impl Fn(u64) -> u64) {
 }
 
 pub fn parse_input(lines: &[String]) -> Result<Vec<Monkey>> {
-    lines
-        .split(|l| l.is_empty())
-        .map(|chunk| Monkey::try_from(chunk))
-        .collect()
+    let monkeys: Vec<Monkey> = utils::parse_blocks(lines)?;
+
+    // both parts multiply the inspections of the two busiest monkeys
+    if monkeys.len() < 2 {
+        return Err(ParseError::new("at least 2 monkeys")
+            .at_line(lines.len())
+            .into());
+    }
+
+    // the targets are on the last two lines of each monkey
+    for ((start, _), monkey) in parser::blocks(lines).zip(&monkeys) {
+        for (j, target) in [monkey.test.true_target, monkey.test.false_target]
+            .into_iter()
+            .enumerate()
+        {
+            if target >= monkeys.len() {
+                return Err(ParseError::new(format!("a monkey below {}", monkeys.len()))
+                    .at_line(start + 4 + j)
+                    .into());
+            }
+        }
+    }
+
+    Ok(monkeys)
 }
 
-pub fn part_one(parsed: &Vec<Monkey>) -> usize {
-    let mut monkeys = parsed.clone();
+pub fn part_one(parsed: &[Monkey]) -> usize {
+    let mut monkeys = parsed.to_vec();
     let len = monkeys.len();
     simulate(&mut monkeys, 20, |x| x / 3);
     monkeys.sort_by_key(|m| m.num_inspections);
     monkeys[len - 1].num_inspections * monkeys[len - 2].num_inspections
 }
 
-pub fn part_two(parsed: &Vec<Monkey>) -> usize {
-    let mut monkeys = parsed.clone();
+pub fn part_two(parsed: &[Monkey]) -> usize {
+    let mut monkeys = parsed.to_vec();
     let len = monkeys.len();
     let divisor: u64 = monkeys.iter().map(|m| m.test.divisible_by).product();
 
@@ -156,6 +196,29 @@ pub fn part_two(parsed: &Vec<Monkey>) -> usize {
     monkeys[len - 1].num_inspections * monkeys[len - 2].num_inspections
 }
 
+pub struct Day11;
+
+impl Day for Day11 {
+    const DAY: u8 = 11;
+    const TITLE: &'static str = "monkey in the middle";
+
+    type Parsed = Vec<Monkey>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(lines: &[String]) -> Result<Self::Parsed> {
+        parse_input(lines)
+    }
+
+    fn part_one(parsed: &Self::Parsed) -> Self::PartOne {
+        part_one(parsed)
+    }
+
+    fn part_two(parsed: &Self::Parsed) -> Self::PartTwo {
+        part_two(parsed)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -174,4 +237,36 @@ mod tests {
         let parsed = parse_input(&lines).expect("could not parse input");
         assert_eq!(part_two(&parsed), 2713310158);
     }
+
+    #[test]
+    fn parse_error_test() {
+        let mut lines = utils::load_input("inputs/day_11.example").expect("could not load input");
+        lines[9] = "  Operation: new".to_owned();
+
+        let actual = parse_input(&lines)
+            .expect_err("should not parse")
+            .downcast::<ParseError>()
+            .expect("should be a parse error");
+        assert_eq!(
+            actual.to_string(),
+            "10:17: expected `  Operation: new = old `"
+        );
+
+        let mut lines = utils::load_input("inputs/day_11.example").expect("could not load input");
+        lines[18] = "    If true: throw to monkey 4".to_owned();
+
+        let actual = parse_input(&lines).expect_err("should not parse");
+        assert_eq!(actual.to_string(), "19: expected a monkey below 4");
+
+        // a short block is reported where it starts
+        let mut lines = utils::load_input("inputs/day_11.example").expect("could not load input");
+        lines.remove(9);
+
+        let actual = parse_input(&lines).expect_err("should not parse");
+        assert_eq!(actual.to_string(), "8: expected a monkey of 6 lines");
+
+        let lines = utils::load_input("inputs/day_11.example").expect("could not load input");
+        let actual = parse_input(&lines[..6]).expect_err("should not parse");
+        assert_eq!(actual.to_string(), "7: expected at least 2 monkeys");
+    }
 }
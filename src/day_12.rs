@@ -1,8 +1,9 @@
-use std::collections::{BinaryHeap, VecDeque};
+use anyhow::Result;
 
-use anyhow::{bail, Error, Result};
-
-use crate::grid::{Coordinate, Grid};
+use crate::grid::{Coordinate, Direction4, Glyph, Grid};
+use crate::search;
+use crate::solution::{Day, Reachable};
+use crate::utils::ParseError;
 
 #[derive(Debug, Clone)]
 pub struct Terrain {
@@ -12,34 +13,29 @@ pub struct Terrain {
 }
 
 impl TryFrom<&[String]> for Terrain {
-    type Error = Error;
+    type Error = ParseError;
 
     fn try_from(lines: &[String]) -> Result<Self, Self::Error> {
         let mut grid: Grid<char> = lines.try_into()?;
-        let mut start = None;
-        let mut end = None;
-
-        'outer: for i in 0..grid.n {
-            for j in 0..grid.m {
-                let coord = (i, j).into();
-                if grid[coord] == 'S' {
-                    start = Some(coord);
-                }
-                if grid[coord] == 'E' {
-                    end = Some(coord);
-                }
-                if start.is_some() && end.is_some() {
-                    break 'outer;
-                }
-            }
+
+        if let Some((coord, _)) = grid
+            .cells()
+            .find(|(_, c)| !c.is_ascii_lowercase() && **c != 'S' && **c != 'E')
+        {
+            return Err(ParseError::new("an elevation (a-z, S or E)")
+                .at_line(coord.row() as usize)
+                .at_offset(coord.col() as usize));
         }
 
+        let start = grid.find_index(|&c| c == 'S');
+        let end = grid.find_index(|&c| c == 'E');
+
         match (start, end) {
             (Some(s), Some(e)) => {
                 grid[s] = 'a';
                 grid[e] = 'z';
             }
-            _ => bail!("Could not find start or end"),
+            _ => return Err(ParseError::new("a start `S` and an end `E`").at_line(lines.len())),
         }
 
         Ok(Terrain {
@@ -52,122 +48,77 @@ impl TryFrom<&[String]> for Terrain {
 }
 
 impl Terrain {
-    fn bfs(&self) -> usize {
-        let mut visited = Grid::new(self.grid.n, self.grid.m, false);
-        let mut q = VecDeque::default();
-        q.push_back((self.start, 0));
-
-        while let Some((coord, dist)) = q.pop_front() {
-            if coord == self.end {
-                return dist;
-            }
-
-            if visited[coord] {
-                continue;
-            }
-
-            visited[coord] = true;
-
-            for neighbour in coord.cardinal_neighbours() {
-                if !self.grid.is_in_bounds(neighbour) {
-                    continue;
-                }
-
-                if self.grid[neighbour] as isize - self.grid[coord] as isize > 1 {
-                    continue;
-                }
-
-                q.push_back((neighbour, dist + 1));
-            }
-        }
-
-        usize::MAX
+    /// Returns the neighbours of [coord] that are at most one step higher
+    fn climbable(&self, coord: &Coordinate) -> Vec<Coordinate> {
+        let height = self.grid[*coord] as isize;
+
+        self.grid
+            .cardinal_neighbours(*coord)
+            .filter(|&n| self.grid[n] as isize - height <= 1)
+            .collect()
     }
 
-    fn dijkstra(&self) -> usize {
-        let mut visited = Grid::new(self.grid.n, self.grid.m, false);
-        let mut acc = Grid::new(self.grid.n, self.grid.m, usize::MAX);
-        let mut q: BinaryHeap<Node> = BinaryHeap::default();
-
-        for i in 0..self.grid.n {
-            for j in 0..self.grid.m {
-                let coord = (i, j).into();
-                if self.grid[coord] == 'a' {
-                    q.push((0, coord).into());
-                    acc[coord] = 0;
-                }
-            }
-        }
-
-        while let Some(node) = q.pop() {
-            let coord = node.coord;
-            if coord == self.end {
-                return acc[coord];
-            }
-
-            if visited[coord] {
-                continue;
-            }
-
-            visited[coord] = true;
-
-            for neighbour in coord.cardinal_neighbours() {
-                if !self.grid.is_in_bounds(neighbour) {
-                    continue;
-                }
+    /// Returns the shortest route from any of the [starts] to the end, both included, or `None`
+    /// if the end cannot be reached
+    pub fn route(&self, starts: impl IntoIterator<Item = Coordinate>) -> Option<Vec<Coordinate>> {
+        search::bfs(starts, |c| self.climbable(c), |c| *c == self.end).map(|path| path.nodes)
+    }
 
-                if self.grid[neighbour] as isize - self.grid[coord] as isize > 1 {
-                    continue;
-                }
+    /// Draws the [route] over the heightmap like the puzzle description does, with an arrow on
+    /// each step showing where it goes next and `E` on the end
+    pub fn render_route(&self, route: &[Coordinate]) -> String {
+        let arrows = route.windows(2).filter_map(|step| {
+            let dir = Direction4::from_offset(step[1] - step[0])?;
+            Some((step[0], dir.glyph()))
+        });
 
-                if acc[coord] + 1 < acc[neighbour] {
-                    acc[neighbour] = acc[coord] + 1;
-                    q.push((acc[neighbour], neighbour).into())
-                }
-            }
-        }
+        self.grid
+            .render_with(|c| *c, arrows.chain([(self.end, 'E')]))
+    }
 
-        acc[self.end]
+    fn lowest_points(&self) -> impl Iterator<Item = Coordinate> + '_ {
+        self.grid.find_all(|&c| c == 'a')
     }
 }
 
-#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
-struct Node {
-    dist: usize,
-    coord: Coordinate,
+pub fn parse_input(lines: &[String]) -> Result<Terrain> {
+    Ok(lines.try_into()?)
 }
 
-impl From<(usize, Coordinate)> for Node {
-    fn from(value: (usize, Coordinate)) -> Self {
-        Self {
-            dist: value.0,
-            coord: value.1,
-        }
-    }
+/// Returns the fewest steps from the start to the end, or `None` if the end can't be reached
+pub fn part_one(parsed: &Terrain) -> Option<usize> {
+    let route = parsed.route([parsed.start])?;
+    Some(route.len() - 1)
 }
 
-impl PartialOrd for Node {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        other.dist.partial_cmp(&self.dist)
-    }
+/// Returns the fewest steps from any of the lowest points to the end, or `None` if the end
+/// can't be reached from any of them
+pub fn part_two(parsed: &Terrain) -> Option<usize> {
+    let route = parsed.route(parsed.lowest_points())?;
+    Some(route.len() - 1)
 }
 
-impl Ord for Node {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        other.dist.cmp(&self.dist)
-    }
-}
+pub struct Day12;
 
-pub fn parse_input(lines: &[String]) -> Result<Terrain> {
-    lines.try_into()
-}
+impl Day for Day12 {
+    const DAY: u8 = 12;
+    const TITLE: &'static str = "hill climbing algorithm";
 
-pub fn part_one(parsed: &Terrain) -> usize {
-    parsed.bfs()
-}
+    type Parsed = Terrain;
+    type PartOne = Reachable<usize>;
+    type PartTwo = Reachable<usize>;
 
-pub fn part_two(parsed: &Terrain) -> usize {
-    parsed.dijkstra()
+    fn parse(lines: &[String]) -> Result<Self::Parsed> {
+        parse_input(lines)
+    }
+
+    fn part_one(parsed: &Self::Parsed) -> Self::PartOne {
+        part_one(parsed).into()
+    }
+
+    fn part_two(parsed: &Self::Parsed) -> Self::PartTwo {
+        part_two(parsed).into()
+    }
 }
 
 #[cfg(test)]
@@ -179,13 +130,39 @@ mod tests {
     fn part_one_test() {
         let lines = utils::load_input("inputs/day_12.example").expect("could not load input");
         let parsed = parse_input(&lines).expect("could not parse input");
-        assert_eq!(part_one(&parsed), 31);
+        assert_eq!(part_one(&parsed), Some(31));
     }
 
     #[test]
     fn part_two_test() {
         let lines = utils::load_input("inputs/day_12.example").expect("could not load input");
         let parsed = parse_input(&lines).expect("could not parse input");
-        assert_eq!(part_two(&parsed), 29);
+        assert_eq!(part_two(&parsed), Some(29));
+    }
+
+    #[test]
+    fn route_test() {
+        let lines = utils::split_lines("Sabcdefghijklm\nEzyxwvutsrqpon");
+        let parsed = parse_input(&lines).expect("could not parse input");
+        let route = parsed.route([parsed.start]).expect("could not find route");
+
+        assert_eq!(route.len(), 28);
+        assert_eq!(route.first(), Some(&parsed.start));
+        assert_eq!(route.last(), Some(&parsed.end));
+        assert_eq!(
+            parsed.render_route(&route),
+            ">>>>>>>>>>>>>v\nE<<<<<<<<<<<<<"
+        );
+
+        // the end is only next to an `a`, which is too steep to climb
+        let lines = utils::split_lines("SaaE");
+        let parsed = parse_input(&lines).expect("could not parse input");
+        assert_eq!(parsed.route([parsed.start]), None);
+
+        // which is reported as an answer rather than a failure
+        let lines = utils::split_lines("Sa\nbE");
+        let parsed = parse_input(&lines).expect("could not parse input");
+        assert_eq!(part_one(&parsed), None);
+        assert_eq!(Day12::part_two(&parsed).to_string(), "unreachable");
     }
 }
@@ -1,6 +1,10 @@
-use anyhow::{anyhow, bail, Error, Result};
+use anyhow::Result;
 use std::str::FromStr;
 
+use crate::parser::{integer, nested_list, Nested, Parser};
+use crate::solution::Day;
+use crate::utils::{self, ParseError};
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 enum PacketData {
     Integer(u64),
@@ -37,14 +41,14 @@ impl PacketData {
             Self::List(_) => self.clone(),
         }
     }
+}
 
-    fn push_to_list(&mut self, value: Self) -> Result<()> {
-        match self {
-            Self::Integer(_) => bail!("Packet data is not a list"),
-            Self::List(l) => l.push(value),
+impl From<Nested<u64>> for PacketData {
+    fn from(value: Nested<u64>) -> Self {
+        match value {
+            Nested::Item(x) => Self::Integer(x),
+            Nested::List(l) => Self::List(l.into_iter().map(Self::from).collect()),
         }
-
-        Ok(())
     }
 }
 
@@ -52,71 +56,11 @@ impl PacketData {
 struct Packet(Vec<PacketData>);
 
 impl FromStr for Packet {
-    type Err = Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut stack = vec![PacketData::List(Vec::default())];
-        let mut start = 0;
-
-        for (i, c) in s.char_indices() {
-            match c {
-                '[' => {
-                    stack.push(PacketData::List(Vec::default()));
-                    start = i + 1;
-                }
-                ']' => {
-                    let mut popped = stack.pop().ok_or_else(|| {
-                        anyhow!("Failed to parse: input {}, index {}, char {}", s, i, c)
-                    })?;
-
-                    if i - start > 0 {
-                        let value: u64 = s[start..i].parse()?;
-                        popped.push_to_list(PacketData::Integer(value))?;
-                    }
-
-                    stack
-                        .last_mut()
-                        .ok_or_else(|| {
-                            anyhow!("Failed to parse: input {}, index {}, char {}", s, i, c)
-                        })?
-                        .push_to_list(popped)?;
-
-                    start = i + 1;
-                }
-                ',' => {
-                    if i - start > 0 {
-                        let value: u64 = s[start..i].parse()?;
-                        stack
-                            .last_mut()
-                            .ok_or_else(|| {
-                                anyhow!("Failed to parse: input {}, index {}, char {}", s, i, c)
-                            })?
-                            .push_to_list(PacketData::Integer(value))?;
-                    }
-
-                    start = i + 1;
-                }
-                ' ' => start = i + 1,
-                _ => {}
-            }
-        }
-
-        let popped = stack
-            .pop()
-            .ok_or_else(|| anyhow!("Mismatched [] brackets"))?;
-
-        match popped {
-            PacketData::Integer(_) => bail!("Expected input to be a list, got integer"),
-            PacketData::List(mut l) => {
-                let inner = l
-                    .pop()
-                    .ok_or_else(|| anyhow!("Expected inner list to exist"))?;
-                match inner {
-                    PacketData::Integer(_) => bail!("Expected input to be a list, got integer"),
-                    PacketData::List(k) => Ok(Packet(k)),
-                }
-            }
-        }
+        let list = nested_list(integer::<u64>()).parse_line(s)?;
+        Ok(Packet(list.into_iter().map(PacketData::from).collect()))
     }
 }
 
@@ -127,27 +71,24 @@ pub struct PacketPair {
 }
 
 impl TryFrom<&[String]> for PacketPair {
-    type Error = Error;
+    type Error = ParseError;
 
     fn try_from(lines: &[String]) -> Result<Self, Self::Error> {
         if lines.len() != 2 {
-            bail!("Packet pair input should be exactly 2 lines");
+            return Err(ParseError::new("a pair of packets").at_line(lines.len()));
         }
 
-        let left = Packet::from_str(&lines[0])?;
-        let right = Packet::from_str(&lines[1])?;
+        let left = Packet::from_str(&lines[0]).map_err(|e| e.at_line(0))?;
+        let right = Packet::from_str(&lines[1]).map_err(|e| e.at_line(1))?;
         Ok(Self { left, right })
     }
 }
 
 pub fn parse_input(lines: &[String]) -> Result<Vec<PacketPair>> {
-    lines
-        .split(|l| l.is_empty())
-        .map(|chunk| chunk.try_into())
-        .collect()
+    Ok(utils::parse_blocks(lines)?)
 }
 
-pub fn part_one(parsed: &Vec<PacketPair>) -> usize {
+pub fn part_one(parsed: &[PacketPair]) -> usize {
     parsed
         .iter()
         .enumerate()
@@ -156,7 +97,7 @@ pub fn part_one(parsed: &Vec<PacketPair>) -> usize {
         .sum()
 }
 
-pub fn part_two(parsed: &Vec<PacketPair>) -> usize {
+pub fn part_two(parsed: &[PacketPair]) -> usize {
     let div1 = Packet(vec![PacketData::List(vec![PacketData::Integer(2)])]);
     let div2 = Packet(vec![PacketData::List(vec![PacketData::Integer(6)])]);
     let mut div1_index = 1;
@@ -183,6 +124,29 @@ pub fn part_two(parsed: &Vec<PacketPair>) -> usize {
     div1_index * div2_index
 }
 
+pub struct Day13;
+
+impl Day for Day13 {
+    const DAY: u8 = 13;
+    const TITLE: &'static str = "distress signal";
+
+    type Parsed = Vec<PacketPair>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(lines: &[String]) -> Result<Self::Parsed> {
+        parse_input(lines)
+    }
+
+    fn part_one(parsed: &Self::Parsed) -> Self::PartOne {
+        part_one(parsed)
+    }
+
+    fn part_two(parsed: &Self::Parsed) -> Self::PartTwo {
+        part_two(parsed)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,83 +1,62 @@
-use anyhow::{anyhow, Error, Result};
+use anyhow::Result;
+
+use crate::grid::{Coordinate, SparseGrid};
+use crate::parser::{self, integer, literal, pair, separated_list, terminated, Failure, Parser};
+use crate::solution::Day;
+use crate::utils::ParseError;
+
+/// Matches a path of points like `498,4 -> 498,6 -> 496,6`, returned as (row, col) pairs
+fn path<'a>() -> impl Parser<'a, Vec<(usize, usize)>> {
+    move |input: &'a str| {
+        let coordinate = pair(terminated(integer(), literal(",")), integer());
+        // keep where each point starts, so a diagonal line can be reported there
+        let point = |at: &'a str| coordinate.parse(at).map(|(p, rest)| ((at, p), rest));
+        let (points, remaining) = separated_list(point, literal(" -> ")).parse(input)?;
+
+        for window in points.windows(2) {
+            let (_, (prev_col, prev_row)) = window[0];
+            let (at, (col, row)) = window[1];
+
+            if row != prev_row && col != prev_col {
+                return Err(Failure::new("a horizontal or vertical line", at));
+            }
+        }
 
-use crate::grid::{Coordinate, Grid};
+        let path = points.into_iter().map(|(_, (col, row))| (row, col));
+        Ok((path.collect(), remaining))
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct Cave {
-    grid: Grid<bool>,
+    grid: SparseGrid<bool>,
     sand_origin: Coordinate,
+    floor: isize,
     num_sand_grains: usize,
 }
 
 impl TryFrom<&[String]> for Cave {
-    type Error = Error;
+    type Error = ParseError;
 
     fn try_from(lines: &[String]) -> Result<Self, Self::Error> {
-        let mut max_row = usize::MIN;
-        let mut min_col = usize::MAX;
-        let mut max_col = usize::MIN;
-        let mut segments: Vec<(Coordinate, Coordinate)> = Vec::default();
-
-        for l in lines {
-            let mut prev_row = 0_usize;
-            let mut prev_col = 0_usize;
-
-            for (i, token) in l.split(" -> ").enumerate() {
-                let (a, b) = token
-                    .split_once(',')
-                    .ok_or_else(|| anyhow!("Could not parse coordinate: {}", token))?;
-                let col: usize = a.parse()?;
-                let row: usize = b.parse()?;
-
-                if i > 0 {
-                    segments.push(((prev_row, prev_col).into(), (row, col).into()));
-                }
-
-                prev_row = row;
-                prev_col = col;
-
-                if row > max_row {
-                    max_row = row;
-                }
-
-                if col < min_col {
-                    min_col = col;
-                }
+        let mut grid = SparseGrid::default();
 
-                if col > max_col {
-                    max_col = col;
+        for path in parser::each_line(lines, path())? {
+            for (&a, &b) in path.iter().skip(1).zip(&path) {
+                for i in a.0.min(b.0)..=a.0.max(b.0) {
+                    for j in a.1.min(b.1)..=a.1.max(b.1) {
+                        grid.insert((i, j).into(), true);
+                    }
                 }
             }
         }
 
-        // extend the sides by 1 column each side so we can correctly simulate for part 2
-        min_col -= 1;
-        max_col += 1;
-        let n = max_row + 3;
-        let mut grid = Grid::new(n, max_col - min_col + 1, false);
-
-        // now fill in the rocks
-        for (a, b) in segments {
-            if a.row() == b.row() {
-                let min = a.col().min(b.col());
-                let max = a.col().max(b.col());
-                for j in min..=max {
-                    let coord = (a.row(), j - min_col as isize).into();
-                    grid[coord] = true;
-                }
-            } else if a.col() == b.col() {
-                let min = a.row().min(b.row());
-                let max = a.row().max(b.row());
-                for i in min..=max {
-                    let coord = (i, a.col() - min_col as isize).into();
-                    grid[coord] = true;
-                }
-            }
-        }
+        let lowest_rock = grid.bounds().map_or(0, |(_, max)| max.row());
 
         Ok(Self {
             grid,
-            sand_origin: (0, 500 - min_col).into(),
+            sand_origin: Coordinate(0, 500),
+            floor: lowest_rock + 2,
             num_sand_grains: 0,
         })
     }
@@ -85,93 +64,35 @@ impl TryFrom<&[String]> for Cave {
 
 impl Cave {
     fn fill_sand(&mut self, bottomless: bool) {
-        loop {
-            match self.drop_sand(bottomless) {
-                Some(coord) => {
-                    self.grid[coord] = true;
-                    self.num_sand_grains += 1;
-
-                    // if the origin is blocked, then we cannot drop any more sand
-                    if coord == self.sand_origin {
-                        break;
-                    }
-                }
-                None => break,
-            }
-        }
-
-        // if there is a floor, add the sides
-        if !bottomless {
-            let h = self.grid.n - 2;
-            let w = self.grid.m;
-            let o = self.sand_origin.col() as usize;
-            let left = h - o;
-            let right = h - (w - o) + 1;
-
-            self.num_sand_grains += (1 + left) * left / 2;
-            self.num_sand_grains += (1 + right) * right / 2;
-        }
-    }
-
-    fn drop_sand(&mut self, bottomless: bool) -> Option<Coordinate> {
-        let mut cur = self.sand_origin.clone();
-        loop {
-            let new_coord = self.tick(cur, bottomless);
-            if new_coord.is_none() {
-                return None;
+        // every grain falls along the path of the previous one until that one came to rest, so
+        // the next grain resumes from the tile right above it instead of from the origin
+        let mut path = vec![self.sand_origin];
+
+        while let Some(&sand) = path.last() {
+            // once it is below the lowest rock, only the floor can stop it
+            if bottomless && sand.row() >= self.floor - 1 {
+                return;
             }
 
-            // safe to unwrap because we return if it was None
-            let unwrapped = new_coord.unwrap();
-            if unwrapped == cur {
-                break;
-            }
+            let next = [sand.south(), sand.southwest(), sand.southeast()]
+                .into_iter()
+                .find(|&c| c.row() < self.floor && !self.grid[c]);
 
-            cur = unwrapped;
-        }
-
-        Some(cur)
-    }
-
-    fn tick(&mut self, sand: Coordinate, bottomless: bool) -> Option<Coordinate> {
-        let candidates = [sand.south(), sand.southwest(), sand.southeast()];
-        for c in candidates {
-            if bottomless {
-                // if we go out of bounds, then it will fall forever
-                if !self.grid.is_in_bounds(c) {
-                    return None;
-                }
-
-                // if the candidate is unoccupied, we will go there
-                if self.grid[c] == false {
-                    return Some(c);
-                }
-            } else {
-                // if we go out of bounds, we assume it's occupied
-                if !self.grid.is_in_bounds(c) {
-                    continue;
-                }
-
-                // if the candidate is on the floor, then we cannot go down anymore
-                let floor = self.grid.n - 1;
-                if c.row() as usize == floor {
-                    return Some(sand);
-                }
-
-                // if the candidate is unoccupied, we will go there
-                if self.grid[c] == false {
-                    return Some(c);
+            match next {
+                Some(c) => path.push(c),
+                None => {
+                    // it comes to rest, and the sand stops once the origin is blocked
+                    self.grid.insert(sand, true);
+                    self.num_sand_grains += 1;
+                    path.pop();
                 }
             }
         }
-
-        // if we didn't return yet, then we couldn't go further, so we rest at the same location
-        Some(sand)
     }
 }
 
 pub fn parse_input(lines: &[String]) -> Result<Cave> {
-    Cave::try_from(lines)
+    Ok(Cave::try_from(lines)?)
 }
 
 pub fn part_one(parsed: &Cave) -> usize {
@@ -186,6 +107,29 @@ pub fn part_two(parsed: &Cave) -> usize {
     cave.num_sand_grains
 }
 
+pub struct Day14;
+
+impl Day for Day14 {
+    const DAY: u8 = 14;
+    const TITLE: &'static str = "regolith reservoir";
+
+    type Parsed = Cave;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(lines: &[String]) -> Result<Self::Parsed> {
+        parse_input(lines)
+    }
+
+    fn part_one(parsed: &Self::Parsed) -> Self::PartOne {
+        part_one(parsed)
+    }
+
+    fn part_two(parsed: &Self::Parsed) -> Self::PartTwo {
+        part_two(parsed)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -204,4 +148,17 @@ mod tests {
         let parsed = parse_input(&lines).expect("could not parse input");
         assert_eq!(part_two(&parsed), 93);
     }
+
+    #[test]
+    fn path_test() {
+        // rock can be anywhere, including in the leftmost column
+        let lines = utils::split_lines("0,2 -> 2,2");
+        let parsed = parse_input(&lines).expect("could not parse input");
+        assert!(parsed.grid[Coordinate(2, 0)]);
+        assert_eq!(parsed.floor, 4);
+
+        let lines = utils::split_lines("498,4 -> 496,6");
+        let err = parse_input(&lines).expect_err("should not parse");
+        assert!(err.to_string().contains("a horizontal or vertical line"));
+    }
 }
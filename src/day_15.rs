@@ -1,8 +1,13 @@
-use anyhow::{anyhow, Error, Result};
+use anyhow::Result;
 use rustc_hash::FxHashMap;
 use std::str::FromStr;
 
+use crate::algebra::Vector2;
 use crate::grid::Coordinate;
+use crate::intervals::{Interval, IntervalSet};
+use crate::parser::{integer, literal, pair, preceded, Parser};
+use crate::solution::Day;
+use crate::utils::{self, ParseError};
 
 #[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
 struct Sensor {
@@ -22,46 +27,37 @@ impl Sensor {
     ) -> Option<Coordinate> {
         // I'm too lazy to do math, so just use the formula here:
         // https://en.wikipedia.org/wiki/Line-line_intersection
-        let (p1, p2) = a;
-        let (p3, p4) = b;
-
+        //
         // We might run into overflow, so just cast to i128. I know it's not the best.
-        let (x1, y1) = (p1.col() as i128, p1.row() as i128);
-        let (x2, y2) = (p2.col() as i128, p2.row() as i128);
-        let (x3, y3) = (p3.col() as i128, p3.row() as i128);
-        let (x4, y4) = (p4.col() as i128, p4.row() as i128);
+        let to_vector = |c: Coordinate| Vector2::new(c.col() as i128, c.row() as i128);
+        let (p1, p2) = (to_vector(a.0), to_vector(a.1));
+        let (p3, p4) = (to_vector(b.0), to_vector(b.1));
 
-        let denom = (x1 - x2) * (y3 - y4) - (y1 - y2) * (x3 - x4);
+        let (d1, d2) = (p1 - p2, p3 - p4);
+        let denom = d1.cross(&d2);
 
         if denom == 0 {
             return None;
         }
 
-        let x_nume = (x1 * y2 - y1 * x2) * (x3 - x4) - (x1 - x2) * (x3 * y4 - y3 * x4);
-        let y_nume = (x1 * y2 - y1 * x2) * (y3 - y4) - (y1 - y2) * (x3 * y4 - y3 * x4);
-        Some(((y_nume / denom) as isize, (x_nume / denom) as isize).into())
+        let nume = d2 * p1.cross(&p2) - d1 * p3.cross(&p4);
+        Some(((nume.y / denom) as isize, (nume.x / denom) as isize).into())
     }
 
-    fn scanned_interval(&self, row: isize) -> Option<(isize, isize)> {
+    fn scanned_interval(&self, row: isize) -> Interval<isize> {
         let diff = (self.position.row() - row).abs();
         let left = self.position.col() - self.radius as isize + diff;
         let right = self.position.col() + self.radius as isize - diff;
 
-        if left >= right {
-            return None;
-        }
-
-        Some((left, right))
+        Interval::new(left, right)
     }
 
     fn perimeter(&self) -> [(Coordinate, Coordinate); 4] {
-        let row = self.position.row();
-        let col = self.position.col();
         let radius = self.radius as isize;
-        let top = (row - radius, col).into();
-        let bottom = (row + radius, col).into();
-        let left = (row, col - radius).into();
-        let right = (row, col + radius).into();
+        let top = self.position + Coordinate(-radius, 0);
+        let bottom = self.position + Coordinate(radius, 0);
+        let left = self.position + Coordinate(0, -radius);
+        let right = self.position + Coordinate(0, radius);
 
         [(top, right), (right, bottom), (bottom, left), (left, top)]
     }
@@ -83,27 +79,32 @@ impl Sensor {
     }
 }
 
+/// Matches a position like `x=2, y=18`, returned as (x, y)
+fn position<'a>() -> impl Parser<'a, (isize, isize)> {
+    pair(
+        preceded(literal("x="), integer()),
+        preceded(literal(", y="), integer()),
+    )
+}
+
 impl FromStr for Sensor {
-    type Err = Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let tokens: Vec<_> = s.split_whitespace().collect();
-        let t1 = tokens.get(2).ok_or_else(|| anyhow!("Invalid input"))?;
-        let sensor_x: isize = t1[2..t1.len() - 1].parse()?;
-        let t2 = tokens.get(3).ok_or_else(|| anyhow!("Invalid input"))?;
-        let sensor_y: isize = t2[2..t2.len() - 1].parse()?;
-        let t3 = tokens.get(8).ok_or_else(|| anyhow!("Invalid input"))?;
-        let beacon_x: isize = t3[2..t3.len() - 1].parse()?;
-        let t4 = tokens.get(9).ok_or_else(|| anyhow!("Invalid input"))?;
-        let beacon_y: isize = t4[2..t4.len()].parse()?;
+        // it looks like:
+        //   Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+        let ((sensor_x, sensor_y), (beacon_x, beacon_y)) = pair(
+            preceded(literal("Sensor at "), position()),
+            preceded(literal(": closest beacon is at "), position()),
+        )
+        .parse_line(s)?;
 
         let position = Coordinate::from((sensor_y, sensor_x));
         let beacon = Coordinate::from((beacon_y, beacon_x));
-        let radius = (beacon_x - sensor_x).abs() + (beacon_y - sensor_y).abs();
         Ok(Sensor {
             position,
             beacon,
-            radius: radius as usize,
+            radius: position.manhattan_distance(&beacon),
         })
     }
 }
@@ -115,84 +116,19 @@ pub struct Sensors {
 }
 
 impl Sensors {
-    fn disjoint_intervals(&self, row: isize) -> Vec<(isize, isize)> {
-        let mut disjoint = Vec::default();
-
-        let mut sorted_intervals: Vec<_> = self
+    fn cleared_positions(&self, row: isize) -> usize {
+        let mut cleared: IntervalSet<isize> = self
             .sensors
             .iter()
-            .filter_map(|s| s.scanned_interval(row))
+            .map(|s| s.scanned_interval(row))
             .collect();
-        sorted_intervals.sort_unstable();
-
-        if sorted_intervals.is_empty() {
-            return disjoint;
-        }
-
-        let mut cur_s = sorted_intervals[0].0;
-        let mut cur_e = sorted_intervals[0].1;
 
-        for i in 1..sorted_intervals.len() {
-            let (s, e) = sorted_intervals[i];
-
-            // interval lies completely inside the ongoing interval, so we do nothing
-            if e <= cur_e {
-                continue;
-            }
-
-            // start of interval is inside the ongoing interval, so we just extend it
-            if s <= cur_e {
-                cur_e = e;
-                continue;
-            }
-
-            // interval lies completely outside the ongoing interval, so we start a new one
-            disjoint.push((cur_s, cur_e));
-            cur_s = s;
-            cur_e = e;
+        // the positions of the beacons on the row are obviously not clear
+        for &col in self.beacons.get(&row).into_iter().flatten() {
+            cleared.remove(col);
         }
 
-        // push the last ongoing interval
-        disjoint.push((cur_s, cur_e));
-        disjoint
-    }
-
-    fn cleared_positions(&self, row: isize) -> usize {
-        let disjoint_intervals = self.disjoint_intervals(row);
-
-        if disjoint_intervals.is_empty() {
-            return 0;
-        }
-
-        let maybe_beacon_cols = self.beacons.get(&row);
-        if maybe_beacon_cols.is_none() || maybe_beacon_cols.unwrap().is_empty() {
-            return disjoint_intervals
-                .iter()
-                .map(|(s, e)| *e - *s + 1)
-                .sum::<isize>() as usize;
-        }
-
-        // safe to unwrap because we checked for None above
-        let beacon_cols = maybe_beacon_cols.unwrap();
-        let mut i = 0;
-        let mut sum = 0;
-        for (s, e) in disjoint_intervals {
-            sum += e - s + 1;
-
-            // if beacon is to be left of start of interval, it would have been processed already,
-            // so we just ignore
-            while i < beacon_cols.len() && beacon_cols[i] < s {
-                i += 1;
-            }
-
-            // next, for every beacon inside the interval, subtract 1 from the sum
-            while i < beacon_cols.len() && beacon_cols[i] <= e {
-                sum -= 1;
-                i += 1;
-            }
-        }
-
-        sum as usize
+        cleared.len() as usize
     }
 
     fn find_distress_signal(&self, upper_bound: isize) -> Option<Coordinate> {
@@ -239,40 +175,22 @@ impl Sensors {
 }
 
 impl TryFrom<&[String]> for Sensors {
-    type Error = Error;
+    type Error = ParseError;
 
     fn try_from(lines: &[String]) -> Result<Self, Self::Error> {
-        let sensors = lines
-            .iter()
-            .map(|l| Sensor::from_str(l))
-            .collect::<Result<Vec<_>>>()?;
-
-        let mut beacons: Vec<_> = sensors.iter().map(|s| s.beacon).collect();
-        beacons.sort_unstable_by_key(|c| c.col());
-
-        let mut beacons_map: FxHashMap<isize, Vec<isize>> = FxHashMap::default();
-        for beacon in beacons {
-            beacons_map
-                .entry(beacon.row())
-                .and_modify(|v| {
-                    if let Some(col) = v.last() {
-                        if *col != beacon.col() {
-                            v.push(beacon.col());
-                        }
-                    }
-                })
-                .or_insert(vec![beacon.col()]);
+        let sensors: Vec<Sensor> = utils::parse_lines(lines)?;
+
+        let mut beacons: FxHashMap<isize, Vec<isize>> = FxHashMap::default();
+        for beacon in sensors.iter().map(|s| s.beacon) {
+            beacons.entry(beacon.row()).or_default().push(beacon.col());
         }
 
-        Ok(Sensors {
-            sensors,
-            beacons: beacons_map,
-        })
+        Ok(Sensors { sensors, beacons })
     }
 }
 
 pub fn parse_input(lines: &[String]) -> Result<Sensors> {
-    Sensors::try_from(lines)
+    Ok(Sensors::try_from(lines)?)
 }
 
 pub fn part_one(parsed: &Sensors) -> usize {
@@ -283,6 +201,29 @@ pub fn part_two(parsed: &Sensors) -> usize {
     parsed.find_tuning_frequency(4000000)
 }
 
+pub struct Day15;
+
+impl Day for Day15 {
+    const DAY: u8 = 15;
+    const TITLE: &'static str = "beacon exclusion zone";
+
+    type Parsed = Sensors;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(lines: &[String]) -> Result<Self::Parsed> {
+        parse_input(lines)
+    }
+
+    fn part_one(parsed: &Self::Parsed) -> Self::PartOne {
+        part_one(parsed)
+    }
+
+    fn part_two(parsed: &Self::Parsed) -> Self::PartTwo {
+        part_two(parsed)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,266 @@
+use anyhow::Result;
+use rustc_hash::FxHashMap;
+use std::str::FromStr;
+
+use crate::parser::{integer, literal, or, pair, preceded, separated_list, take_while1, Parser};
+use crate::solution::Day;
+use crate::utils::{self, ParseError};
+
+/// The valve we start at
+const START: &str = "AA";
+
+/// Matches a valve name of uppercase letters
+fn name<'a>() -> impl Parser<'a, &'a str> {
+    take_while1("a valve name", |c| c.is_ascii_uppercase())
+}
+
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+struct Valve {
+    name: String,
+    rate: usize,
+    tunnels: Vec<String>,
+}
+
+impl FromStr for Valve {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // it looks like either of:
+        //   Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
+        //   Valve HH has flow rate=22; tunnel leads to valve GG
+        let (name, (rate, tunnels)) = pair(
+            preceded(literal("Valve "), name()),
+            pair(
+                preceded(literal(" has flow rate="), integer()),
+                preceded(
+                    or(
+                        literal("; tunnels lead to valves "),
+                        literal("; tunnel leads to valve "),
+                    ),
+                    separated_list(name(), literal(", ")),
+                ),
+            ),
+        )
+        .parse_line(s)?;
+
+        Ok(Self {
+            name: name.to_string(),
+            rate,
+            tunnels: tunnels.into_iter().map(String::from).collect(),
+        })
+    }
+}
+
+/// The valves worth opening, along with the travel time between each pair of them
+///
+/// The start is kept as well even if its flow rate is 0, since that is where we travel from
+/// first.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Volcano {
+    /// The flow rates of the valves worth opening, which come first in [distances]
+    rates: Vec<usize>,
+    /// The number of minutes it takes to walk from one valve to another
+    distances: Vec<Vec<usize>>,
+    start: usize,
+}
+
+impl Volcano {
+    /// Finds the most pressure that can be released in [minutes] while opening exactly the
+    /// valves of each set, where bit `i` of the index stands for the valve `i`
+    fn pressure_by_opened(&self, minutes: usize) -> Vec<usize> {
+        let mut best = vec![0; 1 << self.rates.len()];
+        self.explore(self.start, minutes, 0, 0, &mut best);
+        best
+    }
+
+    fn explore(
+        &self,
+        valve: usize,
+        minutes: usize,
+        opened: usize,
+        pressure: usize,
+        best: &mut [usize],
+    ) {
+        best[opened] = best[opened].max(pressure);
+
+        for (next, &rate) in self.rates.iter().enumerate() {
+            // walking there and opening the valve must leave time for it to release something
+            let cost = self.distances[valve][next].saturating_add(1);
+            if opened & (1 << next) != 0 || cost >= minutes {
+                continue;
+            }
+
+            let left = minutes - cost;
+            self.explore(
+                next,
+                left,
+                opened | (1 << next),
+                pressure + rate * left,
+                best,
+            );
+        }
+    }
+
+    fn most_pressure(&self, minutes: usize) -> usize {
+        self.pressure_by_opened(minutes)
+            .into_iter()
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// Like [Volcano::most_pressure], but with an elephant opening valves at the same time
+    fn most_pressure_with_elephant(&self, minutes: usize) -> usize {
+        let mut best = self.pressure_by_opened(minutes);
+
+        // make every set hold the best of its subsets, so that we can pair any set we open with
+        // whatever the elephant does best among the remaining valves
+        for i in 0..self.rates.len() {
+            for opened in 0..best.len() {
+                if opened & (1 << i) != 0 {
+                    best[opened] = best[opened].max(best[opened ^ (1 << i)]);
+                }
+            }
+        }
+
+        let all = best.len() - 1;
+        (0..best.len())
+            .map(|opened| best[opened] + best[all ^ opened])
+            .max()
+            .unwrap_or_default()
+    }
+}
+
+impl TryFrom<&[String]> for Volcano {
+    type Error = ParseError;
+
+    fn try_from(lines: &[String]) -> Result<Self, Self::Error> {
+        let valves: Vec<Valve> = utils::parse_lines(lines)?;
+        let indices: FxHashMap<&str, usize> = valves
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (v.name.as_str(), i))
+            .collect();
+
+        // shortest travel times between every pair of valves
+        let n = valves.len();
+        let mut distances = vec![vec![usize::MAX; n]; n];
+
+        for (i, valve) in valves.iter().enumerate() {
+            distances[i][i] = 0;
+
+            for tunnel in &valve.tunnels {
+                let &j = indices
+                    .get(tunnel.as_str())
+                    .ok_or_else(|| ParseError::new("a known valve").at_line(i))?;
+                distances[i][j] = 1;
+            }
+        }
+
+        for k in 0..n {
+            for i in 0..n {
+                for j in 0..n {
+                    let through = distances[i][k].saturating_add(distances[k][j]);
+                    distances[i][j] = distances[i][j].min(through);
+                }
+            }
+        }
+
+        // only the valves worth opening and the start are kept, the others are just walked past
+        let mut kept: Vec<usize> = (0..n).filter(|&i| valves[i].rate > 0).collect();
+        let rates = kept.iter().map(|&i| valves[i].rate).collect();
+
+        let &start = indices
+            .get(START)
+            .ok_or_else(|| ParseError::new(format!("a valve named {}", START)).at_line(n))?;
+        let start = match kept.iter().position(|&i| i == start) {
+            Some(start) => start,
+            None => {
+                kept.push(start);
+                kept.len() - 1
+            }
+        };
+
+        Ok(Self {
+            rates,
+            distances: kept
+                .iter()
+                .map(|&i| kept.iter().map(|&j| distances[i][j]).collect())
+                .collect(),
+            start,
+        })
+    }
+}
+
+pub fn parse_input(lines: &[String]) -> Result<Volcano> {
+    Ok(Volcano::try_from(lines)?)
+}
+
+pub fn part_one(parsed: &Volcano) -> usize {
+    parsed.most_pressure(30)
+}
+
+pub fn part_two(parsed: &Volcano) -> usize {
+    parsed.most_pressure_with_elephant(26)
+}
+
+pub struct Day16;
+
+impl Day for Day16 {
+    const DAY: u8 = 16;
+    const TITLE: &'static str = "proboscidea volcanium";
+
+    type Parsed = Volcano;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(lines: &[String]) -> Result<Self::Parsed> {
+        parse_input(lines)
+    }
+
+    fn part_one(parsed: &Self::Parsed) -> Self::PartOne {
+        part_one(parsed)
+    }
+
+    fn part_two(parsed: &Self::Parsed) -> Self::PartTwo {
+        part_two(parsed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils;
+
+    #[test]
+    fn part_one_test() {
+        let lines = utils::load_input("inputs/day_16.example").expect("could not load input");
+        let parsed = parse_input(&lines).expect("could not parse input");
+        assert_eq!(part_one(&parsed), 1651);
+    }
+
+    #[test]
+    fn part_two_test() {
+        let lines = utils::load_input("inputs/day_16.example").expect("could not load input");
+        let parsed = parse_input(&lines).expect("could not parse input");
+        assert_eq!(part_two(&parsed), 1707);
+    }
+
+    #[test]
+    fn parse_test() {
+        let lines = utils::load_input("inputs/day_16.example").expect("could not load input");
+        let parsed = parse_input(&lines).expect("could not parse input");
+
+        // BB, CC, DD, EE, HH and JJ are worth opening, and AA is kept last as the start
+        assert_eq!(parsed.rates, vec![13, 2, 20, 3, 22, 21]);
+        assert_eq!(parsed.start, 6);
+        assert_eq!(parsed.distances[6], vec![1, 2, 1, 2, 5, 2, 0]);
+
+        let lines = utils::split_lines("Valve AA has flow rate=0; tunnel leads to valve BB");
+        let err = parse_input(&lines).expect_err("should not parse");
+        assert!(err.to_string().contains("a known valve"));
+
+        let lines = utils::split_lines("Valve BB has flow rate=1; tunnel leads to valve BB");
+        let err = parse_input(&lines).expect_err("should not parse");
+        assert!(err.to_string().contains("a valve named AA"));
+    }
+}
@@ -1,50 +1,46 @@
-use anyhow::{bail, Error, Result};
+use anyhow::Result;
 use rustc_hash::FxHashMap;
 use std::str::FromStr;
 use std::{collections::VecDeque, fmt};
 
-use crate::grid::Coordinate;
+use crate::grid::{Coordinate, Direction4};
+use crate::solution::Day;
+use crate::utils::ParseError;
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
-enum Direction {
-    Left,
-    Right,
-}
-
-impl TryFrom<char> for Direction {
-    type Error = Error;
-
-    fn try_from(value: char) -> Result<Self, Self::Error> {
-        match value {
-            '<' => Ok(Self::Left),
-            '>' => Ok(Self::Right),
-            _ => bail!("Invalid direction"),
-        }
+/// Parses a jet of hot gas, which only ever pushes to the west or east
+fn jet(c: char) -> Result<Direction4, ParseError> {
+    match Direction4::try_from(c) {
+        Ok(dir @ (Direction4::West | Direction4::East)) => Ok(dir),
+        _ => Err(ParseError::new("a direction (< or >)")),
     }
 }
 
 #[derive(Debug, Clone, Default, Eq, PartialEq)]
 struct Wind {
-    pattern: Vec<Direction>,
+    pattern: Vec<Direction4>,
     index: usize,
 }
 
 impl FromStr for Wind {
-    type Err = Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(ParseError::new("a direction (< or >)").at_offset(0));
+        }
+
         Ok(Self {
             pattern: s
-                .chars()
-                .map(|c| Direction::try_from(c))
-                .collect::<Result<Vec<_>>>()?,
+                .char_indices()
+                .map(|(i, c)| jet(c).map_err(|e| e.at_offset(i)))
+                .collect::<Result<Vec<_>, _>>()?,
             index: 0,
         })
     }
 }
 
 impl Iterator for Wind {
-    type Item = Direction;
+    type Item = Direction4;
 
     fn next(&mut self) -> Option<Self::Item> {
         self.index %= self.pattern.len();
@@ -101,6 +97,15 @@ struct Piece {
     position: Coordinate,
 }
 
+impl Piece {
+    fn shifted(&self, by: Coordinate) -> Self {
+        Self {
+            position: self.position + by,
+            ..*self
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
 struct Sky {
     index: usize,
@@ -210,26 +215,13 @@ impl Tetris {
     fn tick(&mut self, piece: &mut Piece) -> bool {
         let dir = self.wind.next().expect("cound not get next wind direction");
 
-        // first apply the wind
-        match dir {
-            Direction::Left => {
-                // try to move left and check for out of bounds or collision
-                piece.position.1 -= 1;
-                if piece.position.1 < 0 || self.collides_with(piece) {
-                    // move back if we are out of bounds or collided
-                    piece.position.1 += 1;
-                }
-            }
-            Direction::Right => {
-                // try to move right and check for out of bounds or collision
-                piece.position.1 += 1;
-                if (piece.position.1 as usize + piece.shape.width()) > Self::WELL_WIDTH
-                    || self.collides_with(piece)
-                {
-                    // move back if we are out of bounds or collided
-                    piece.position.1 -= 1;
-                }
-            }
+        // first apply the wind, moving only if we stay in bounds and don't collide
+        let pushed = piece.shifted(dir.offset());
+        if pushed.position.col() >= 0
+            && pushed.position.col() as usize + pushed.shape.width() <= Self::WELL_WIDTH
+            && !self.collides_with(&pushed)
+        {
+            *piece = pushed;
         }
 
         // if we are on the floor, then we cannot move down
@@ -238,12 +230,11 @@ impl Tetris {
         }
 
         // try to move down and check for collision
-        piece.position.0 += 1;
-        if self.collides_with(piece) {
-            // move back up if we collide
-            piece.position.0 -= 1;
+        let dropped = piece.shifted(Direction4::South.offset());
+        if self.collides_with(&dropped) {
             false
         } else {
+            *piece = dropped;
             true
         }
     }
@@ -253,9 +244,9 @@ impl Tetris {
     fn settle(&mut self, piece: &Piece) {
         let piece_rows = piece.shape.rows();
 
-        for i in 0..piece.shape.height() {
+        for (i, piece_row) in piece_rows.iter().take(piece.shape.height()).enumerate() {
             let row = piece.position.row() as usize + i;
-            let piece_mask = piece_rows[i] >> piece.position.col();
+            let piece_mask = piece_row >> piece.position.col();
             self.well[row] |= piece_mask;
         }
 
@@ -274,10 +265,10 @@ impl Tetris {
     fn collides_with(&self, piece: &Piece) -> bool {
         let piece_rows = piece.shape.rows();
 
-        for i in 0..piece.shape.height() {
+        for (i, piece_row) in piece_rows.iter().take(piece.shape.height()).enumerate() {
             let row = piece.position.row() as usize + i;
             let well_mask = self.well[row];
-            let piece_mask = piece_rows[i] >> piece.position.col();
+            let piece_mask = piece_row >> piece.position.col();
 
             if well_mask & piece_mask > 0 {
                 return true;
@@ -292,7 +283,7 @@ impl fmt::Display for Tetris {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         for i in 0..self.well.len() {
             let row = &self.well[i];
-            write!(f, "{:>13} |{:#010b}|\n", i, row)?
+            writeln!(f, "{:>13} |{:#010b}|", i, row)?
         }
 
         Ok(())
@@ -301,12 +292,14 @@ impl fmt::Display for Tetris {
 
 pub fn parse_input(lines: &[String]) -> Result<Tetris> {
     if lines.len() != 1 {
-        bail!("Input should have exactly 1 line");
+        return Err(ParseError::new("exactly 1 line")
+            .at_line(lines.len().min(1))
+            .into());
     }
 
     Ok(Tetris {
         sky: Sky::default(),
-        wind: Wind::from_str(&lines[0])?,
+        wind: Wind::from_str(&lines[0]).map_err(|e| e.at_line(0))?,
         well: VecDeque::default(),
     })
 }
@@ -321,6 +314,29 @@ pub fn part_two(parsed: &Tetris) -> usize {
     tetris.simulate(1000000000000)
 }
 
+pub struct Day17;
+
+impl Day for Day17 {
+    const DAY: u8 = 17;
+    const TITLE: &'static str = "pyroclastic flow";
+
+    type Parsed = Tetris;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(lines: &[String]) -> Result<Self::Parsed> {
+        parse_input(lines)
+    }
+
+    fn part_one(parsed: &Self::Parsed) -> Self::PartOne {
+        part_one(parsed)
+    }
+
+    fn part_two(parsed: &Self::Parsed) -> Self::PartTwo {
+        part_two(parsed)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,8 +1,10 @@
-use std::str::FromStr;
-
-use anyhow::{Error, Result};
+use anyhow::Result;
 
 use crate::algebra::Point3;
+use crate::grid::Grid3;
+use crate::search;
+use crate::solution::Day;
+use crate::utils::{self, ParseError};
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 enum Block {
@@ -11,13 +13,10 @@ enum Block {
     Lava,
 }
 
-#[derive(Debug, Clone, Default, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Lava {
     points: Vec<Point3>,
-    grid: Vec<Vec<Vec<Block>>>,
-    n: usize,
-    m: usize,
-    l: usize,
+    grid: Grid3<Block>,
 }
 
 impl Lava {
@@ -27,10 +26,7 @@ impl Lava {
             count += p
                 .cardinal_neighbours()
                 .iter()
-                .filter(|&q| {
-                    let (i, j, k) = (q.x as usize, q.y as usize, q.z as usize);
-                    !self.is_in_bounds(q) || self.grid[i][j][k] != Block::Lava
-                })
+                .filter(|&&q| self.grid.get(q) != Some(&Block::Lava))
                 .count()
         }
 
@@ -45,10 +41,7 @@ impl Lava {
             count += p
                 .cardinal_neighbours()
                 .iter()
-                .filter(|&q| {
-                    let (i, j, k) = (q.x as usize, q.y as usize, q.z as usize);
-                    !self.is_in_bounds(q) || self.grid[i][j][k] == Block::Exterior
-                })
+                .filter(|&&q| matches!(self.grid.get(q), None | Some(Block::Exterior)))
                 .count()
         }
 
@@ -56,115 +49,61 @@ impl Lava {
     }
 
     fn fill_exterior(&mut self) {
-        // do dfs from all 6 sides
-        for i in 0..self.n {
-            for j in 0..self.m {
-                self.dfs(&Point3::new(i as i64, j as i64, 0));
-            }
-        }
-
-        for i in 0..self.n {
-            for j in 0..self.m {
-                self.dfs(&Point3::new(i as i64, j as i64, self.l as i64 - 1));
-            }
-        }
-
-        for i in 0..self.n {
-            for k in 0..self.l {
-                self.dfs(&Point3::new(i as i64, 0, k as i64));
-            }
-        }
-
-        for i in 0..self.n {
-            for k in 0..self.l {
-                self.dfs(&Point3::new(i as i64, self.m as i64 - 1, k as i64));
-            }
-        }
-
-        for j in 0..self.m {
-            for k in 0..self.l {
-                self.dfs(&Point3::new(0, j as i64, k as i64));
-            }
-        }
-
-        for j in 0..self.m {
-            for k in 0..self.l {
-                self.dfs(&Point3::new(self.n as i64 - 1, j as i64, k as i64));
-            }
+        // flood fill from every block on the 6 sides that isn't lava
+        let sides = self
+            .grid
+            .find_all(|&b| b != Block::Lava)
+            .filter(|&p| self.grid.is_on_edge(p))
+            .collect::<Vec<_>>();
+
+        let exterior = search::flood_fill(sides, |&p| {
+            self.grid
+                .cardinal_neighbours(p)
+                .filter(|&q| self.grid[q] != Block::Lava)
+                .collect::<Vec<_>>()
+        });
+
+        for p in exterior {
+            self.grid[p] = Block::Exterior;
         }
     }
+}
 
-    fn dfs(&mut self, p: &Point3) {
-        if !self.is_in_bounds(p) {
-            return;
-        }
+impl TryFrom<&[String]> for Lava {
+    type Error = ParseError;
 
-        let (i, j, k) = (p.x as usize, p.y as usize, p.z as usize);
+    fn try_from(lines: &[String]) -> Result<Self, Self::Error> {
+        let points: Vec<Point3> = utils::parse_lines(lines)?;
 
-        if self.grid[i][j][k] == Block::Exterior || self.grid[i][j][k] == Block::Lava {
-            return;
+        if points.is_empty() {
+            return Err(ParseError::new("at least one cube").at_line(0));
         }
 
-        self.grid[i][j][k] = Block::Exterior;
-
-        for neighbour in p.cardinal_neighbours() {
-            self.dfs(&neighbour);
+        if let Some(i) = points.iter().position(|p| p.x < 0 || p.y < 0 || p.z < 0) {
+            return Err(ParseError::new("non-negative coordinates").at_line(i));
         }
-    }
-
-    fn is_in_bounds(&self, p: &Point3) -> bool {
-        p.x >= 0
-            && p.x < self.n as i64
-            && p.y >= 0
-            && p.y < self.m as i64
-            && p.z >= 0
-            && p.z < self.l as i64
-    }
-}
 
-impl TryFrom<&[String]> for Lava {
-    type Error = Error;
-
-    fn try_from(lines: &[String]) -> Result<Self, Self::Error> {
-        let points = lines
-            .iter()
-            .map(|l| Point3::from_str(l))
-            .collect::<Result<Vec<_>, _>>()?;
-
-        let (mut n, mut m, mut l) = (i64::MIN, i64::MIN, i64::MIN);
+        let (mut n, mut m, mut l) = (0, 0, 0);
 
         for p in &points {
-            n = n.max(p.x);
-            m = m.max(p.y);
-            l = l.max(p.z);
+            n = n.max(p.x as usize + 1);
+            m = m.max(p.y as usize + 1);
+            l = l.max(p.z as usize + 1);
         }
 
-        n += 1;
-        m += 1;
-        l += 1;
-
-        // We are assuming that we don't have negative coordinates. If we do, then you will need
-        // to do some extra math to calculate the grid size and the indices when accessing the
-        // blocks.
-        let mut grid = vec![vec![vec![Block::Interior; l as usize]; m as usize]; n as usize];
+        // negative coordinates were rejected above, so every point fits in the grid as is
+        let mut grid = Grid3::new(n, m, l, Block::Interior);
 
-        for p in &points {
-            let (i, j, k) = (p.x as usize, p.y as usize, p.z as usize);
-            grid[i][j][k] = Block::Lava;
+        for &p in &points {
+            grid[p] = Block::Lava;
         }
 
-        Ok(Lava {
-            points,
-            grid,
-            n: n as usize,
-            m: m as usize,
-            l: l as usize,
-        })
+        Ok(Lava { points, grid })
     }
 }
 
 pub fn parse_input(lines: &[String]) -> Result<Lava> {
-    Lava::try_from(lines)
+    Ok(Lava::try_from(lines)?)
 }
 
 pub fn part_one(parsed: &Lava) -> usize {
@@ -176,6 +115,29 @@ pub fn part_two(parsed: &Lava) -> usize {
     lava.exterior_surface_area()
 }
 
+pub struct Day18;
+
+impl Day for Day18 {
+    const DAY: u8 = 18;
+    const TITLE: &'static str = "boiling boulders";
+
+    type Parsed = Lava;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(lines: &[String]) -> Result<Self::Parsed> {
+        parse_input(lines)
+    }
+
+    fn part_one(parsed: &Self::Parsed) -> Self::PartOne {
+        part_one(parsed)
+    }
+
+    fn part_two(parsed: &Self::Parsed) -> Self::PartTwo {
+        part_two(parsed)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
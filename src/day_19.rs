@@ -0,0 +1,272 @@
+use anyhow::Result;
+use std::str::FromStr;
+
+use crate::parser::{delimited, integer, literal, pair, terminated, Parser};
+use crate::solution::Day;
+use crate::utils::{self, ParseError};
+
+const ORE: usize = 0;
+const CLAY: usize = 1;
+const OBSIDIAN: usize = 2;
+const GEODE: usize = 3;
+
+/// What each robot costs, indexed by robot then by the resource spent
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct Blueprint {
+    id: usize,
+    costs: [[usize; 3]; 4],
+    /// The most of each resource that can be spent in a minute, since there's no point in
+    /// collecting more than that
+    max_robots: [usize; 3],
+}
+
+impl Blueprint {
+    fn new(id: usize, costs: [[usize; 3]; 4]) -> Self {
+        let mut max_robots = [0; 3];
+        for cost in &costs {
+            for (max, &c) in max_robots.iter_mut().zip(cost) {
+                *max = (*max).max(c);
+            }
+        }
+
+        Self {
+            id,
+            costs,
+            max_robots,
+        }
+    }
+
+    /// Returns the most geodes that can be opened in [minutes], starting with a single ore robot
+    fn max_geodes(&self, minutes: usize) -> usize {
+        let mut best = 0;
+        self.explore(
+            State {
+                minutes,
+                resources: [0; 3],
+                robots: [1, 0, 0],
+                geodes: 0,
+            },
+            &mut best,
+        );
+
+        best
+    }
+
+    /// Branches on which robot to build next, waiting as long as needed to afford it
+    fn explore(&self, state: State, best: &mut usize) {
+        *best = (*best).max(state.geodes);
+
+        if self.upper_bound(&state) <= *best {
+            return;
+        }
+
+        // building geode robots first finds good answers early, which prunes the rest harder
+        for robot in [GEODE, OBSIDIAN, CLAY, ORE] {
+            if let Some(next) = self.build(&state, robot) {
+                self.explore(next, best);
+            }
+        }
+    }
+
+    /// Waits until [robot] is affordable and builds it, unless it's not worth building
+    fn build(&self, state: &State, robot: usize) -> Option<State> {
+        if robot != GEODE && state.robots[robot] >= self.max_robots[robot] {
+            return None;
+        }
+
+        let cost = self.costs[robot];
+        let mut wait = 0;
+
+        for resource in [ORE, CLAY, OBSIDIAN] {
+            if state.resources[resource] >= cost[resource] {
+                continue;
+            }
+
+            // we can't wait for a resource we don't collect yet
+            let robots = state.robots[resource];
+            if robots == 0 {
+                return None;
+            }
+
+            wait = wait.max((cost[resource] - state.resources[resource]).div_ceil(robots));
+        }
+
+        // the robot needs to be built with at least a minute to spare to collect anything
+        if wait + 1 >= state.minutes {
+            return None;
+        }
+
+        let minutes = state.minutes - wait - 1;
+        let mut next = State { minutes, ..*state };
+
+        for resource in [ORE, CLAY, OBSIDIAN] {
+            next.resources[resource] += state.robots[resource] * (wait + 1);
+            next.resources[resource] -= cost[resource];
+        }
+
+        // a geode robot's geodes are all counted as soon as it is built
+        if robot == GEODE {
+            next.geodes += minutes;
+        } else {
+            next.robots[robot] += 1;
+        }
+
+        Some(next)
+    }
+
+    /// An optimistic number of geodes for [state], where ore and clay are free and a new
+    /// obsidian robot is built every minute
+    fn upper_bound(&self, state: &State) -> usize {
+        let cost = self.costs[GEODE][OBSIDIAN];
+        let mut obsidian = state.resources[OBSIDIAN];
+        let mut geodes = state.geodes;
+
+        let robots = state.robots[OBSIDIAN]..;
+        for (robots, minutes) in robots.zip((0..state.minutes).rev()) {
+            if obsidian >= cost {
+                obsidian -= cost;
+                geodes += minutes;
+            }
+
+            obsidian += robots;
+        }
+
+        geodes
+    }
+
+    fn quality_level(&self, minutes: usize) -> usize {
+        self.id * self.max_geodes(minutes)
+    }
+}
+
+impl FromStr for Blueprint {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // it looks like (on a single line):
+        //   Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. Each obsidian
+        //   robot costs 3 ore and 14 clay. Each geode robot costs 2 ore and 7 obsidian.
+        let costs = |robot: &'static str, resource: &'static str, end: &'static str| {
+            pair(
+                delimited(literal(robot), integer(), literal(resource)),
+                terminated(integer(), literal(end)),
+            )
+        };
+
+        let (id, (ore, (clay, (obsidian, geode)))) = pair(
+            delimited(literal("Blueprint "), integer(), literal(": ")),
+            pair(
+                delimited(
+                    literal("Each ore robot costs "),
+                    integer(),
+                    literal(" ore. "),
+                ),
+                pair(
+                    delimited(
+                        literal("Each clay robot costs "),
+                        integer(),
+                        literal(" ore. "),
+                    ),
+                    pair(
+                        costs("Each obsidian robot costs ", " ore and ", " clay. "),
+                        costs("Each geode robot costs ", " ore and ", " obsidian."),
+                    ),
+                ),
+            ),
+        )
+        .parse_line(s)?;
+
+        Ok(Self::new(
+            id,
+            [
+                [ore, 0, 0],
+                [clay, 0, 0],
+                [obsidian.0, obsidian.1, 0],
+                [geode.0, 0, geode.1],
+            ],
+        ))
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+struct State {
+    /// The minutes left
+    minutes: usize,
+    /// The ore, clay and obsidian collected so far
+    resources: [usize; 3],
+    /// The ore, clay and obsidian robots built so far
+    robots: [usize; 3],
+    /// The geodes that will have been opened by the end, given the geode robots built so far
+    geodes: usize,
+}
+
+pub fn parse_input(lines: &[String]) -> Result<Vec<Blueprint>> {
+    Ok(utils::parse_lines(lines)?)
+}
+
+pub fn part_one(parsed: &[Blueprint]) -> usize {
+    parsed.iter().map(|b| b.quality_level(24)).sum()
+}
+
+pub fn part_two(parsed: &[Blueprint]) -> usize {
+    parsed.iter().take(3).map(|b| b.max_geodes(32)).product()
+}
+
+pub struct Day19;
+
+impl Day for Day19 {
+    const DAY: u8 = 19;
+    const TITLE: &'static str = "not enough minerals";
+
+    type Parsed = Vec<Blueprint>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(lines: &[String]) -> Result<Self::Parsed> {
+        parse_input(lines)
+    }
+
+    fn part_one(parsed: &Self::Parsed) -> Self::PartOne {
+        part_one(parsed)
+    }
+
+    fn part_two(parsed: &Self::Parsed) -> Self::PartTwo {
+        part_two(parsed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils;
+
+    #[test]
+    fn part_one_test() {
+        let lines = utils::load_input("inputs/day_19.example").expect("could not load input");
+        let parsed = parse_input(&lines).expect("could not parse input");
+        assert_eq!(part_one(&parsed), 33);
+    }
+
+    #[test]
+    fn part_two_test() {
+        let lines = utils::load_input("inputs/day_19.example").expect("could not load input");
+        let parsed = parse_input(&lines).expect("could not parse input");
+        assert_eq!(part_two(&parsed), 3472);
+    }
+
+    #[test]
+    fn blueprint_test() {
+        let lines = utils::load_input("inputs/day_19.example").expect("could not load input");
+        let parsed = parse_input(&lines).expect("could not parse input");
+
+        assert_eq!(parsed[0].costs[GEODE], [2, 0, 7]);
+        assert_eq!(parsed[0].max_robots, [4, 14, 7]);
+        assert_eq!(parsed[0].max_geodes(24), 9);
+        assert_eq!(parsed[1].max_geodes(24), 12);
+        assert_eq!(parsed[0].max_geodes(32), 56);
+        assert_eq!(parsed[1].max_geodes(32), 62);
+
+        let lines = utils::split_lines("Blueprint 1: Each ore robot costs 4 ore.");
+        assert!(parse_input(&lines).is_err());
+    }
+}
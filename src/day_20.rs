@@ -1,4 +1,8 @@
-use anyhow::{anyhow, Error, Ok, Result};
+use anyhow::{anyhow, Result};
+
+use crate::parser::{self, integer};
+use crate::solution::Day;
+use crate::utils::ParseError;
 
 #[derive(Debug, Clone, Default, Eq, PartialEq)]
 pub struct Sequence {
@@ -28,7 +32,7 @@ impl Sequence {
 
     fn decrypt(&mut self) {
         for (_, x) in self.values.iter_mut() {
-            *x = *x * 811589153;
+            *x *= 811589153;
         }
     }
 
@@ -76,13 +80,10 @@ impl Sequence {
 }
 
 impl TryFrom<&[String]> for Sequence {
-    type Error = Error;
+    type Error = ParseError;
 
     fn try_from(lines: &[String]) -> Result<Self, Self::Error> {
-        let tmp: Vec<i64> = lines
-            .iter()
-            .map(|l| l.parse())
-            .collect::<Result<Vec<_>, _>>()?;
+        let tmp: Vec<i64> = parser::each_line(lines, integer())?;
 
         let values = tmp.iter().enumerate().map(|(i, v)| (i, *v)).collect();
 
@@ -91,7 +92,7 @@ impl TryFrom<&[String]> for Sequence {
 }
 
 pub fn parse_input(lines: &[String]) -> Result<Sequence> {
-    Sequence::try_from(lines)
+    Ok(Sequence::try_from(lines)?)
 }
 
 pub fn part_one(parsed: &Sequence) -> i64 {
@@ -111,6 +112,29 @@ pub fn part_two(parsed: &Sequence) -> i64 {
     sequence.coordinates().expect("Could not find coordinates")
 }
 
+pub struct Day20;
+
+impl Day for Day20 {
+    const DAY: u8 = 20;
+    const TITLE: &'static str = "grove positioning system";
+
+    type Parsed = Sequence;
+    type PartOne = i64;
+    type PartTwo = i64;
+
+    fn parse(lines: &[String]) -> Result<Self::Parsed> {
+        parse_input(lines)
+    }
+
+    fn part_one(parsed: &Self::Parsed) -> Self::PartOne {
+        part_one(parsed)
+    }
+
+    fn part_two(parsed: &Self::Parsed) -> Self::PartTwo {
+        part_two(parsed)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,6 +1,11 @@
-use anyhow::{bail, Error, Result};
+use anyhow::{bail, Result};
 use rustc_hash::FxHashMap;
-use std::str::FromStr;
+
+use crate::parser::{
+    self, integer, key_value, literal, map, or, pair, preceded, take_while1, try_map, Parser,
+};
+use crate::solution::Day;
+use crate::utils::ParseError;
 
 fn id_to_usize(s: &str) -> usize {
     let mut ret = 0;
@@ -13,6 +18,17 @@ fn id_to_usize(s: &str) -> usize {
     ret
 }
 
+/// Matches a monkey name of 4 lowercase letters and converts it into its numeric form
+fn name<'a>() -> impl Parser<'a, usize> {
+    try_map(
+        take_while1("a name of 4 lowercase letters", |c| c.is_ascii_lowercase()),
+        |id| match id.len() {
+            4 => Ok(id_to_usize(id)),
+            _ => Err(ParseError::new("a name of 4 lowercase letters").at_offset(0)),
+        },
+    )
+}
+
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 enum Operator {
     Add,
@@ -97,32 +113,23 @@ enum Expression {
     Div(usize, usize),
 }
 
-impl FromStr for Expression {
-    type Err = Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let tokens: Vec<_> = s.split_whitespace().collect();
-
-        if tokens.len() == 1 {
-            return Ok(Self::Number(tokens[0].parse()?));
-        }
-
-        if tokens.len() != 3 {
-            bail!("Invalid expression: {}", s);
-        }
-
-        let a = id_to_usize(tokens[0]);
-        let b = id_to_usize(tokens[2]);
-
-        let expr = match tokens[1] {
-            "+" => Self::Add(a, b),
-            "-" => Self::Sub(a, b),
-            "*" => Self::Mult(a, b),
-            "/" => Self::Div(a, b),
-            _ => bail!("Invalid operator: {}", tokens[1]),
+impl Expression {
+    fn parser<'a>() -> impl Parser<'a, Self> {
+        let operator = |symbol: &'static str, f: fn(usize, usize) -> Self| {
+            map(preceded(literal(symbol), name()), move |b| (f, b))
         };
-
-        Ok(expr)
+        let operation = pair(
+            name(),
+            or(
+                or(operator(" + ", Self::Add), operator(" - ", Self::Sub)),
+                or(operator(" * ", Self::Mult), operator(" / ", Self::Div)),
+            ),
+        );
+
+        or(
+            map(integer(), Self::Number),
+            map(operation, |(a, (f, b))| f(a, b)),
+        )
     }
 }
 
@@ -140,7 +147,7 @@ impl Riddle {
     }
 
     fn evaluate_helper(&self, var: &usize) -> Result<i64> {
-        if self.bindings.get(var).is_none() {
+        if !self.bindings.contains_key(var) {
             bail!("Could not find binding for var: {}", var);
         }
 
@@ -158,7 +165,7 @@ impl Riddle {
     fn solve(&self) -> Result<i64> {
         let var = &Self::ROOT;
 
-        if self.bindings.get(var).is_none() {
+        if !self.bindings.contains_key(var) {
             bail!("Could not find binding for var: {}", var);
         }
 
@@ -185,7 +192,7 @@ impl Riddle {
     }
 
     fn solve_helper(&self, var: &usize) -> Result<EvalResult> {
-        if self.bindings.get(var).is_none() {
+        if !self.bindings.contains_key(var) {
             bail!("Could not find binding for var: {}", var);
         }
 
@@ -253,22 +260,19 @@ impl Riddle {
 }
 
 impl TryFrom<&[String]> for Riddle {
-    type Error = Error;
+    type Error = ParseError;
 
     fn try_from(lines: &[String]) -> Result<Self, Self::Error> {
-        let mut bindings = FxHashMap::default();
+        let bindings = parser::each_line(lines, key_value(name(), Expression::parser()))?;
 
-        for l in lines {
-            let tokens: Vec<_> = l.split(": ").collect();
-            bindings.insert(id_to_usize(tokens[0]), Expression::from_str(tokens[1])?);
-        }
-
-        Ok(Riddle { bindings })
+        Ok(Riddle {
+            bindings: bindings.into_iter().collect(),
+        })
     }
 }
 
 pub fn parse_input(lines: &[String]) -> Result<Riddle> {
-    Riddle::try_from(lines)
+    Ok(Riddle::try_from(lines)?)
 }
 
 pub fn part_one(parsed: &Riddle) -> i64 {
@@ -279,6 +283,29 @@ pub fn part_two(parsed: &Riddle) -> i64 {
     parsed.solve().expect("Could not solve")
 }
 
+pub struct Day21;
+
+impl Day for Day21 {
+    const DAY: u8 = 21;
+    const TITLE: &'static str = "monkey math";
+
+    type Parsed = Riddle;
+    type PartOne = i64;
+    type PartTwo = i64;
+
+    fn parse(lines: &[String]) -> Result<Self::Parsed> {
+        parse_input(lines)
+    }
+
+    fn part_one(parsed: &Self::Parsed) -> Self::PartOne {
+        part_one(parsed)
+    }
+
+    fn part_two(parsed: &Self::Parsed) -> Self::PartTwo {
+        part_two(parsed)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
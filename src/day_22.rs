@@ -1,10 +1,16 @@
-use anyhow::{anyhow, bail, Error, Result};
-use std::str::FromStr;
+use anyhow::Result;
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::fmt;
 
-use crate::grid::{Coordinate, Grid};
+use crate::algebra::Vector3;
+use crate::grid::{Coordinate, Direction4, Glyph, Grid, Turn};
+use crate::parser::{any_char, integer, many, map, opt, pair, try_map, Parser};
+use crate::solution::{Day, Reachable};
+use crate::utils::ParseError;
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Default)]
 enum Tile {
+    #[default]
     Void,
     Wall,
     Open {
@@ -17,34 +23,19 @@ enum Tile {
 
 impl Tile {
     fn is_open(&self) -> bool {
-        match self {
-            Self::Open { .. } => true,
-            _ => false,
-        }
+        matches!(self, Self::Open { .. })
     }
 
     fn is_wall(&self) -> bool {
-        match self {
-            Self::Wall => true,
-            _ => false,
-        }
+        matches!(self, Self::Wall)
     }
     fn is_void(&self) -> bool {
-        match self {
-            Self::Void => true,
-            _ => false,
-        }
-    }
-}
-
-impl Default for Tile {
-    fn default() -> Self {
-        Tile::Void
+        matches!(self, Self::Void)
     }
 }
 
 impl TryFrom<char> for Tile {
-    type Error = Error;
+    type Error = ParseError;
 
     fn try_from(value: char) -> Result<Self, Self::Error> {
         Ok(match value {
@@ -56,92 +47,252 @@ impl TryFrom<char> for Tile {
                 west: isize::MIN,
                 east: isize::MAX,
             },
-            _ => bail!("Invalid char"),
+            _ => return Err(ParseError::new("a tile (space, # or .)")),
         })
     }
 }
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
-enum Turn {
-    Clockwise,
-    Counterclockwise,
+impl Glyph for Tile {
+    fn glyph(&self) -> char {
+        match self {
+            Self::Void => ' ',
+            Self::Wall => '#',
+            Self::Open { .. } => '.',
+        }
+    }
+
+    fn from_glyph(c: char) -> Result<Self, ParseError> {
+        Self::try_from(c)
+    }
 }
 
-impl TryFrom<char> for Turn {
-    type Error = Error;
+/// The value of facing [dir] in the final password
+fn facing(dir: Direction4) -> usize {
+    match dir {
+        Direction4::East => 0,
+        Direction4::South => 1,
+        Direction4::West => 2,
+        Direction4::North => 3,
+    }
+}
 
-    fn try_from(value: char) -> Result<Self, Self::Error> {
-        Ok(match value {
-            'R' => Self::Clockwise,
-            'L' => Self::Counterclockwise,
-            _ => bail!("Invalid char"),
-        })
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+struct Instruction {
+    steps: usize,
+    turn: Turn,
+}
+
+impl Instruction {
+    /// Matches a number of steps, optionally followed by a turn. The last instruction of the route
+    /// has no turn, so it just gets a dummy one.
+    fn parser<'a>() -> impl Parser<'a, Self> {
+        map(
+            pair(integer(), opt(try_map(any_char(), Turn::try_from))),
+            |(steps, turn)| Self {
+                steps,
+                turn: turn.unwrap_or(Turn::Clockwise),
+            },
+        )
     }
 }
 
+/// A face of the cube, along with where the directions of the map point to once it is folded
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
-enum Direction {
-    North,
-    South,
-    West,
-    East,
+struct Face {
+    /// The top left tile of the face on the map
+    origin: Coordinate,
+    /// Points out of the cube
+    normal: Vector3,
+    east: Vector3,
+    south: Vector3,
 }
 
-impl Direction {
-    fn turn(&self, turn: &Turn) -> Self {
-        match (self, turn) {
-            (Self::North, Turn::Clockwise) => Self::East,
-            (Self::East, Turn::Clockwise) => Self::South,
-            (Self::South, Turn::Clockwise) => Self::West,
-            (Self::West, Turn::Clockwise) => Self::North,
-            (Self::North, Turn::Counterclockwise) => Self::West,
-            (Self::East, Turn::Counterclockwise) => Self::North,
-            (Self::South, Turn::Counterclockwise) => Self::East,
-            (Self::West, Turn::Counterclockwise) => Self::South,
+impl Face {
+    fn towards(&self, dir: Direction4) -> Vector3 {
+        match dir {
+            Direction4::North => -self.south,
+            Direction4::East => self.east,
+            Direction4::South => self.south,
+            Direction4::West => -self.east,
         }
     }
 
-    fn value(&self) -> usize {
-        match self {
-            Self::East => 0,
-            Self::South => 1,
-            Self::West => 2,
-            Self::North => 3,
+    /// Folds the face at [origin], which is next to this one in [dir] on the map, down along
+    /// their shared edge
+    fn fold(&self, origin: Coordinate, dir: Direction4) -> Self {
+        let (east, south) = match dir {
+            Direction4::North => (self.east, self.normal),
+            Direction4::East => (-self.normal, self.south),
+            Direction4::South => (self.east, -self.normal),
+            Direction4::West => (self.normal, self.south),
+        };
+
+        Self {
+            origin,
+            normal: self.towards(dir),
+            east,
+            south,
         }
     }
 }
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
-struct Instruction {
-    steps: usize,
-    turn: Turn,
+/// The map folded into a cube, with the edges of each face stitched to the face they meet
+#[derive(Debug, Clone)]
+struct Cube {
+    size: usize,
+    faces: Vec<Face>,
+    /// Maps walking off a face in a direction to the face we walk onto and the direction we then
+    /// face
+    edges: FxHashMap<(usize, Direction4), (usize, Direction4)>,
 }
 
-impl FromStr for Instruction {
-    type Err = Error;
+impl Cube {
+    /// Finds the faces of any of the 11 cube nets, or returns `None` if the map doesn't fold into
+    /// a cube
+    fn fold(grid: &Grid<Tile>) -> Option<Self> {
+        let area = grid.cells().filter(|(_, tile)| !tile.is_void()).count();
+        let size = (1..=area)
+            .find(|s| 6 * s * s >= area)
+            .filter(|s| 6 * s * s == area)?;
+        let len = size as isize;
+
+        let is_face = |origin: Coordinate| {
+            (0..len).all(|i| {
+                (0..len).all(|j| {
+                    grid.get(origin + Coordinate(i, j))
+                        .is_some_and(|tile| !tile.is_void())
+                })
+            })
+        };
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let res = s
-            .chars()
-            .rev()
-            .next()
-            .ok_or_else(|| anyhow!("Invalid instruction"))?
-            .try_into();
-
-        Ok(match res {
-            // if we could parse the turn, then we are still in the middle of the line
-            Ok(turn) => Self {
-                steps: s[0..s.len() - 1].parse()?,
-                turn,
-            },
-            // if we couldn't parse the turn, then we are at the end of the line, just use a dummy
-            // value
-            Err(_) => Self {
-                steps: s.parse()?,
-                turn: Turn::Clockwise,
-            },
+        let start = (0..grid.n)
+            .step_by(size)
+            .flat_map(|i| (0..grid.m).step_by(size).map(move |j| (i, j).into()))
+            .find(|&origin| is_face(origin))?;
+
+        // walk the net from the first face, folding each new face down along the edge we reached
+        // it through
+        let mut faces = vec![Face {
+            origin: start,
+            normal: Vector3::new(0, 0, 1),
+            east: Vector3::new(1, 0, 0),
+            south: Vector3::new(0, -1, 0),
+        }];
+        let mut i = 0;
+
+        while i < faces.len() {
+            let face = faces[i];
+
+            for dir in Direction4::ALL {
+                let origin = face.origin + dir.offset() * len;
+                if is_face(origin) && faces.iter().all(|f| f.origin != origin) {
+                    faces.push(face.fold(origin, dir));
+                }
+            }
+
+            i += 1;
+        }
+
+        // every tile belongs to a face, but the faces could still overlap once folded
+        let normals: FxHashSet<_> = faces.iter().map(|f| f.normal).collect();
+        if faces.len() != 6 || normals.len() != 6 {
+            return None;
+        }
+
+        // number the faces in reading order, like the puzzle description does
+        faces.sort_unstable_by_key(|f| (f.origin.row(), f.origin.col()));
+
+        // walking off a face takes us to the face it points towards, and we then walk away from
+        // the face we came from
+        let mut edges = FxHashMap::default();
+        for (i, face) in faces.iter().enumerate() {
+            for dir in Direction4::ALL {
+                let j = faces.iter().position(|f| f.normal == face.towards(dir))?;
+                let entering = Direction4::ALL
+                    .into_iter()
+                    .find(|&d| faces[j].towards(d) == -face.normal)?;
+
+                edges.insert((i, dir), (j, entering));
+            }
+        }
+
+        Some(Self { size, faces, edges })
+    }
+
+    /// Returns the index of the face that [position] is on
+    fn face_of(&self, position: Coordinate) -> Option<usize> {
+        let len = self.size as isize;
+        self.faces.iter().position(|f| {
+            let local = position - f.origin;
+            (0..len).contains(&local.row()) && (0..len).contains(&local.col())
         })
     }
+
+    /// Walks off the edge of the face at [position] in [dir], returning where we land and the
+    /// direction we then face
+    fn wrap(&self, position: Coordinate, dir: Direction4) -> (Coordinate, Direction4) {
+        let len = self.size as isize;
+        let i = self
+            .face_of(position)
+            .expect("Could not find the face of the position");
+        let local = position - self.faces[i].origin;
+
+        // how far along the edge we are, going clockwise around the face
+        let along = match dir {
+            Direction4::North => local.col(),
+            Direction4::East => local.row(),
+            Direction4::South => len - 1 - local.col(),
+            Direction4::West => len - 1 - local.row(),
+        };
+
+        // both faces agree on which way clockwise goes along the edge, so we land just as far
+        // along the opposite side of the new face
+        let (j, entering) = self.edges[&(i, dir)];
+        let local = match entering {
+            Direction4::North => Coordinate(len - 1, along),
+            Direction4::East => Coordinate(along, 0),
+            Direction4::South => Coordinate(0, len - 1 - along),
+            Direction4::West => Coordinate(len - 1 - along, len - 1),
+        };
+
+        (self.faces[j].origin + local, entering)
+    }
+}
+
+/// Crossing from one face of the cube to another, with faces numbered from 1 in reading order
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Transition {
+    pub faces: (usize, usize),
+    /// The last tile of the face we left and the direction we left it in
+    pub leaving: (Coordinate, Direction4),
+    /// The first tile of the face we entered and the direction we then face
+    pub entering: (Coordinate, Direction4),
+}
+
+impl fmt::Display for Transition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ((from, leaving), (to, entering)) = (self.leaving, self.entering);
+        write!(
+            f,
+            "face {} at ({}, {}) facing {:?} -> face {} at ({}, {}) facing {:?}",
+            self.faces.0,
+            from.row(),
+            from.col(),
+            leaving,
+            self.faces.1,
+            to.row(),
+            to.col(),
+            entering
+        )
+    }
+}
+
+/// Every tile visited while following the route, in order and with the direction faced there
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Trace {
+    pub steps: Vec<(Coordinate, Direction4)>,
+    /// Only filled in when walking on the cube
+    pub transitions: Vec<Transition>,
 }
 
 #[derive(Debug, Clone)]
@@ -153,6 +304,7 @@ pub struct Board {
     col_bounds: Vec<(usize, usize)>,
     start: Coordinate,
     route: Vec<Instruction>,
+    cube: Option<Cube>,
 }
 
 impl Board {
@@ -164,18 +316,18 @@ impl Board {
             let wall_row = &self.wall_rows[i];
             let mut wall_row_index = 0;
 
-            for j in 0..self.grid.m {
+            for (j, wall_col_count) in wall_col_indices.iter_mut().enumerate() {
                 let tile = &mut self.grid[(i, j).into()];
 
                 let (north_bound, south_bound) = self.col_bounds[j];
                 let wall_col = &self.wall_cols[j];
-                let wall_col_index = wall_col_indices[j];
+                let wall_col_index = *wall_col_count;
 
                 match tile {
                     Tile::Void => continue,
                     Tile::Wall => {
-                        wall_row_index = wall_row_index + 1;
-                        wall_col_indices[j] = wall_col_index + 1;
+                        wall_row_index += 1;
+                        *wall_col_count += 1;
                     }
                     Tile::Open {
                         north,
@@ -212,16 +364,19 @@ impl Board {
         }
     }
 
-
-    fn wrap_around_2d(&self, position: &Coordinate, dir: &Direction) -> Option<(Coordinate, Direction)> {
+    fn wrap_around_2d(
+        &self,
+        position: &Coordinate,
+        dir: &Direction4,
+    ) -> Option<(Coordinate, Direction4)> {
         let row = position.row() as usize;
         let col = position.col() as usize;
 
         let coord = match dir {
-            Direction::East => (row, self.row_bounds[row].0).into(),
-            Direction::West => (row, self.row_bounds[row].1).into(),
-            Direction::South => (self.col_bounds[col].0, col).into(),
-            Direction::North => (self.col_bounds[col].1, col).into(),
+            Direction4::East => (row, self.row_bounds[row].0).into(),
+            Direction4::West => (row, self.row_bounds[row].1).into(),
+            Direction4::South => (self.col_bounds[col].0, col).into(),
+            Direction4::North => (self.col_bounds[col].1, col).into(),
         };
 
         Some((coord, *dir))
@@ -230,273 +385,210 @@ impl Board {
     fn wrap_around_3d(
         &self,
         position: &Coordinate,
-        dir: &Direction,
-    ) -> Option<(Coordinate, Direction)> {
-        // XXX jackhxs 2022-12-27: We are making the assumption that the input always follow this
-        // layout:
-        //
-        //     #########
-        //     # 1 # 2 #
-        //     #########
-        //     # 3 #
-        // #########
-        // # 4 # 5 #
-        // #########
-        // # 6 #
-        // #####
-
-        let row = position.row() as usize;
-        let col = position.col() as usize;
-        let edge_len = self.grid.m / 3;
-
-        // 1 north edge <-> 6 west edge
-        if *dir == Direction::North && row == 0 && (edge_len..edge_len * 2).contains(&col) {
-            let i = col - edge_len;
-            return Some(((edge_len * 3 + i, 0).into(), Direction::East));
-        }
-
-        if *dir == Direction::West && col == 0 && (edge_len * 3..edge_len * 4).contains(&row) {
-            let i = row - edge_len * 3;
-            return Some(((0, edge_len + i).into(), Direction::South));
-        }
-
-        // 2 north edge <-> 6 south edge
-        if *dir == Direction::North && row == 0 && (edge_len * 2..edge_len * 3).contains(&col) {
-            let i = col - edge_len * 2;
-            return Some(((edge_len * 4 - 1, i).into(), Direction::North));
-        }
-
-        if *dir == Direction::South && row == edge_len * 4 - 1 && (0..edge_len).contains(&col) {
-            let i = col;
-            return Some(((0, edge_len * 2 + i).into(), Direction::South));
-        }
-
-        // 1 west edge <-> 4 west edge
-        if *dir == Direction::West && col == edge_len && (0..edge_len).contains(&row) {
-            let i = row;
-            return Some(((edge_len * 3 - 1 - i, 0).into(), Direction::East));
-        }
-
-        if *dir == Direction::West && col == 0 && (edge_len * 2..edge_len * 3).contains(&row) {
-            let i = row - edge_len * 2;
-            return Some(((edge_len - 1 - i, edge_len).into(), Direction::East));
-        }
-
-        // 2 east edge <-> 5 east edge
-        if *dir == Direction::East && col == edge_len * 3 - 1 && (0..edge_len).contains(&row) {
-            let i = row;
-            return Some((
-                (edge_len * 3 - 1 - i, edge_len * 2 - 1).into(),
-                Direction::West,
-            ));
-        }
-
-        if *dir == Direction::East
-            && col == edge_len * 2 - 1
-            && (edge_len * 2..edge_len * 3).contains(&row)
-        {
-            let i = row - edge_len * 2;
-            return Some(((edge_len - 1 - i, edge_len * 3 - 1).into(), Direction::West));
-        }
-
-        // 2 south edge <-> 3 east edge
-        if *dir == Direction::South
-            && row == edge_len - 1
-            && (edge_len * 2..edge_len * 3).contains(&col)
-        {
-            let i = col - edge_len * 2;
-            return Some(((edge_len + i, edge_len * 2 - 1).into(), Direction::West));
-        }
-
-        if *dir == Direction::East
-            && col == edge_len * 2 - 1
-            && (edge_len..edge_len * 2).contains(&row)
-        {
-            let i = row - edge_len;
-            return Some(((edge_len - 1, edge_len * 2 + i).into(), Direction::North));
-        }
-
-        // 3 west edge <-> 4 north edge
-        if *dir == Direction::West && col == edge_len && (edge_len..edge_len * 2).contains(&row) {
-            let i = row - edge_len;
-            return Some(((edge_len * 2, i).into(), Direction::South));
-        }
+        dir: &Direction4,
+    ) -> Option<(Coordinate, Direction4)> {
+        self.cube.as_ref().map(|cube| cube.wrap(*position, *dir))
+    }
 
-        if *dir == Direction::North && row == edge_len * 2 && (0..edge_len).contains(&col) {
-            let i = col;
-            return Some(((edge_len + i, edge_len).into(), Direction::East));
-        }
+    fn traverse(&self, is_cube: bool) -> usize {
+        let (position, dir) = self.walk(is_cube, &mut None);
+        1000 * (position.row() as usize + 1) + 4 * (position.col() as usize + 1) + facing(dir)
+    }
 
-        // 5 south edge <-> 6 east edge
-        if *dir == Direction::South
-            && row == edge_len * 3 - 1
-            && (edge_len..edge_len * 2).contains(&col)
-        {
-            let i = col - edge_len;
-            return Some(((edge_len * 3 + i, edge_len - 1).into(), Direction::West));
+    /// Follows the route like [Board::traverse], but records every tile visited on the way and,
+    /// on a cube, every crossing from one face to another
+    pub fn trace(&self, is_cube: bool) -> Trace {
+        let mut trace = Some(Trace::default());
+        self.walk(is_cube, &mut trace);
+        let mut trace = trace.unwrap_or_default();
+
+        if let Some(cube) = self.cube.as_ref().filter(|_| is_cube) {
+            trace.transitions = trace
+                .steps
+                .windows(2)
+                .filter_map(|w| {
+                    let (from, to) = (cube.face_of(w[0].0)?, cube.face_of(w[1].0)?);
+                    (from != to).then_some(Transition {
+                        faces: (from + 1, to + 1),
+                        leaving: w[0],
+                        entering: w[1],
+                    })
+                })
+                .collect();
         }
 
-        if *dir == Direction::East
-            && col == edge_len - 1
-            && (edge_len * 3..edge_len * 4).contains(&row)
-        {
-            let i = row - edge_len * 3;
-            return Some(((edge_len * 3 - 1, edge_len + i).into(), Direction::North));
-        }
+        trace
+    }
 
-        None
+    /// Draws the board with an arrow on every tile of the [trace], showing the direction we last
+    /// faced there like the puzzle description does
+    pub fn render_trace(&self, trace: &Trace) -> String {
+        self.grid.render_with(
+            Glyph::glyph,
+            trace.steps.iter().map(|&(coord, dir)| (coord, dir.glyph())),
+        )
     }
 
-    fn traverse(&self, is_cube: bool) -> usize {
+    /// Follows the route, returning where we end up and the direction we face
+    fn walk(&self, is_cube: bool, trace: &mut Option<Trace>) -> (Coordinate, Direction4) {
         let mut position = self.start;
-        let mut dir = Direction::East;
+        let mut dir = Direction4::East;
+
+        if let Some(trace) = trace {
+            trace.steps.push((position, dir));
+        }
 
         for instruction in &self.route {
-            self.advance(&mut (*instruction).clone(), &mut position, &mut dir, is_cube);
-            dir = dir.turn(&instruction.turn);
+            self.advance(
+                &mut (*instruction).clone(),
+                &mut position,
+                &mut dir,
+                is_cube,
+                trace,
+            );
+            dir = dir.turn(instruction.turn);
+
+            if let Some(trace) = trace {
+                trace.steps.push((position, dir));
+            }
         }
 
         // turn back the last dummy turn
-        dir = dir.turn(&Turn::Counterclockwise);
+        dir = dir.turn(Turn::Counterclockwise);
+
+        if let Some(step) = trace.as_mut().and_then(|t| t.steps.last_mut()) {
+            *step = (position, dir);
+        }
 
-        1000 * (position.row() as usize + 1) + 4 * (position.col() as usize + 1) + dir.value()
+        (position, dir)
     }
 
     fn advance(
         &self,
         instruction: &mut Instruction,
         position: &mut Coordinate,
-        dir: &mut Direction,
+        dir: &mut Direction4,
         is_cube: bool,
+        trace: &mut Option<Trace>,
     ) {
         let (row, col) = (position.row(), position.col());
+        let from = *position;
         let tile = self.grid[*position];
 
         let Tile::Open {
             north: north_stop,
             south: south_stop,
             west: west_stop,
-            east: east_stop
-        } = tile else {
+            east: east_stop,
+        } = tile
+        else {
             // If we hit a wall, then we do nothing. This will only happen on boundary connections.
             return;
         };
 
-        match dir {
-            Direction::East => {
+        // we move to the last tile before a wall or the edge of the map, and only wrap around if
+        // we reached the edge with steps to spare
+        let at_edge = match dir {
+            Direction4::East => {
                 if east_stop - col >= instruction.steps as isize {
                     position.1 += instruction.steps as isize;
+                    false
                 } else if east_stop < self.row_bounds[row as usize].1 as isize {
                     position.1 = east_stop;
+                    false
                 } else {
                     instruction.steps -= (east_stop - col + 1) as usize;
                     position.1 = east_stop;
-
-                    let wrapped = if is_cube {
-                        self.wrap_around_3d(position, dir)
-                    } else {
-                        self.wrap_around_2d(position, dir)
-                    };
-
-                    if let Some((new_position, new_dir)) = wrapped {
-                        if self.grid[new_position].is_open() {
-                            *position = new_position;
-                            *dir = new_dir;
-                            self.advance(instruction, position, dir, is_cube);
-                        }
-                    }
+                    true
                 }
             }
-            Direction::South => {
+            Direction4::South => {
                 if south_stop - row >= instruction.steps as isize {
                     position.0 += instruction.steps as isize;
+                    false
                 } else if south_stop < self.col_bounds[col as usize].1 as isize {
                     position.0 = south_stop;
+                    false
                 } else {
                     instruction.steps -= (south_stop - row + 1) as usize;
                     position.0 = south_stop;
-
-                    let wrapped = if is_cube {
-                        self.wrap_around_3d(position, dir)
-                    } else {
-                        self.wrap_around_2d(position, dir)
-                    };
-
-                    if let Some((new_position, new_dir)) = wrapped {
-                        if self.grid[new_position].is_open() {
-                            *position = new_position;
-                            *dir = new_dir;
-                            self.advance(instruction, position, dir, is_cube);
-                        }
-                    }
+                    true
                 }
             }
-            Direction::West => {
+            Direction4::West => {
                 if col - west_stop >= instruction.steps as isize {
                     position.1 -= instruction.steps as isize;
+                    false
                 } else if west_stop > self.row_bounds[row as usize].0 as isize {
                     position.1 = west_stop;
+                    false
                 } else {
                     instruction.steps -= (col - west_stop + 1) as usize;
                     position.1 = west_stop;
-
-                    let wrapped = if is_cube {
-                        self.wrap_around_3d(position, dir)
-                    } else {
-                        self.wrap_around_2d(position, dir)
-                    };
-
-                    if let Some((new_position, new_dir)) = wrapped {
-                        if self.grid[new_position].is_open() {
-                            *position = new_position;
-                            *dir = new_dir;
-                            self.advance(instruction, position, dir, is_cube);
-                        }
-                    }
+                    true
                 }
             }
-            Direction::North => {
+            Direction4::North => {
                 if row - north_stop >= instruction.steps as isize {
                     position.0 -= instruction.steps as isize;
+                    false
                 } else if north_stop > self.col_bounds[col as usize].0 as isize {
                     position.0 = north_stop;
+                    false
                 } else {
                     instruction.steps -= (row - north_stop + 1) as usize;
                     position.0 = north_stop;
+                    true
+                }
+            }
+        };
 
-                    let wrapped = if is_cube {
-                        self.wrap_around_3d(position, dir)
-                    } else {
-                        self.wrap_around_2d(position, dir)
-                    };
-
-                    if let Some((new_position, new_dir)) = wrapped {
-                        if self.grid[new_position].is_open() {
-                            *position = new_position;
-                            *dir = new_dir;
-                            self.advance(instruction, position, dir, is_cube);
-                        }
-                    }
+        if let Some(trace) = trace {
+            let steps = from.manhattan_distance(position);
+            trace
+                .steps
+                .extend((1..=steps).map(|n| (from.step(*dir, n), *dir)));
+        }
+
+        if !at_edge {
+            return;
+        }
+
+        let wrapped = if is_cube {
+            self.wrap_around_3d(position, dir)
+        } else {
+            self.wrap_around_2d(position, dir)
+        };
+
+        if let Some((new_position, new_dir)) = wrapped {
+            if self.grid[new_position].is_open() {
+                *position = new_position;
+                *dir = new_dir;
+
+                if let Some(trace) = trace {
+                    trace.steps.push((new_position, new_dir));
                 }
+
+                self.advance(instruction, position, dir, is_cube, trace);
             }
         }
     }
-
 }
 
 impl TryFrom<&[String]> for Board {
-    type Error = Error;
+    type Error = ParseError;
 
     fn try_from(lines: &[String]) -> Result<Self, Self::Error> {
         let mut chunks = lines.split(|l| l.is_empty());
 
-        let grid_lines = chunks.next().ok_or_else(|| anyhow!("Invalid input"))?;
+        // split always returns at least one chunk
+        let grid_lines = chunks.next().unwrap_or_default();
         let n = grid_lines.len();
         let m = grid_lines
             .iter()
             .map(|l| l.len())
             .max()
-            .ok_or_else(|| anyhow!("Invalid input"))?;
+            .ok_or_else(|| ParseError::new("a map").at_line(0))?;
 
         let mut grid = vec![vec![Tile::default(); m]; n];
         let mut wall_rows = vec![Vec::default(); n];
@@ -509,7 +601,7 @@ impl TryFrom<&[String]> for Board {
             row_bounds[i].1 = l.len() - 1;
 
             for (j, c) in l.char_indices() {
-                let tile = Tile::try_from(c)?;
+                let tile = Tile::try_from(c).map_err(|e| e.at_line(i).at_offset(j))?;
 
                 if tile.is_wall() {
                     wall_rows[i].push(j);
@@ -546,22 +638,27 @@ impl TryFrom<&[String]> for Board {
             }
         }
 
-        let route = chunks
+        let route_line = chunks
             .next()
-            .and_then(|lines| lines.get(0))
-            .ok_or_else(|| anyhow!("Invalid input"))?
-            .split_inclusive(&['L', 'R'])
-            .map(|t| Instruction::from_str(t))
-            .collect::<Result<Vec<_>, _>>()?;
+            .and_then(|lines| lines.first())
+            .ok_or_else(|| ParseError::new("a route after the map").at_line(n + 1))?;
+        let route = many(Instruction::parser())
+            .parse_line(route_line)
+            .map_err(|e| e.at_line(n + 1))?;
+
+        // part one only walks the flat map, so a map that doesn't fold is still a valid board
+        let grid = grid.into();
+        let cube = Cube::fold(&grid);
 
         Ok(Board {
-            grid: grid.into(),
-            start: start.ok_or_else(|| anyhow!("Invalid input"))?,
+            grid,
+            start: start.ok_or_else(|| ParseError::new("an open tile").at_line(0))?,
             wall_rows,
             wall_cols,
             row_bounds,
             col_bounds,
             route,
+            cube,
         })
     }
 }
@@ -576,8 +673,32 @@ pub fn part_one(parsed: &Board) -> usize {
     parsed.traverse(false)
 }
 
-pub fn part_two(parsed: &Board) -> usize {
-    parsed.traverse(true)
+/// Returns `None` if the map doesn't fold into a cube
+pub fn part_two(parsed: &Board) -> Option<usize> {
+    parsed.cube.is_some().then(|| parsed.traverse(true))
+}
+
+pub struct Day22;
+
+impl Day for Day22 {
+    const DAY: u8 = 22;
+    const TITLE: &'static str = "monkey map";
+
+    type Parsed = Board;
+    type PartOne = usize;
+    type PartTwo = Reachable<usize>;
+
+    fn parse(lines: &[String]) -> Result<Self::Parsed> {
+        parse_input(lines)
+    }
+
+    fn part_one(parsed: &Self::Parsed) -> Self::PartOne {
+        part_one(parsed)
+    }
+
+    fn part_two(parsed: &Self::Parsed) -> Self::PartTwo {
+        part_two(parsed).into()
+    }
 }
 
 #[cfg(test)]
@@ -594,10 +715,84 @@ mod tests {
 
     #[test]
     fn part_two_test() {
-        // example has a different layout, so we use a custom example that follows the same layout
-        // as the real input
+        let lines = utils::load_input("inputs/day_22.example").expect("could not load input");
+        let parsed = parse_input(&lines).expect("could not parse input");
+        assert_eq!(part_two(&parsed), Some(5031));
+
+        // the custom example is folded from a different net
         let lines = utils::load_input("inputs/day_22.custom").expect("could not load input");
         let parsed = parse_input(&lines).expect("could not parse input");
-        assert_eq!(part_two(&parsed), 4028);
+        assert_eq!(part_two(&parsed), Some(4028));
+    }
+
+    #[test]
+    fn trace_test() {
+        let lines = utils::load_input("inputs/day_22.example").expect("could not load input");
+        let parsed = parse_input(&lines).expect("could not parse input");
+
+        let trace = parsed.trace(false);
+        assert!(trace.transitions.is_empty());
+        assert_eq!(
+            trace.steps.last(),
+            Some(&(Coordinate(5, 7), Direction4::East))
+        );
+
+        let trace = parsed.trace(true);
+        let rendered = parsed.render_trace(&trace);
+        let rendered: Vec<_> = rendered.lines().map(|l| l.trim_end()).collect();
+        assert_eq!(
+            rendered,
+            vec![
+                "        >>v#",
+                "        .#v.",
+                "        #.v.",
+                "        ..v.",
+                "...#..^...v#",
+                ".>>>>>^.#.>>",
+                ".^#....#....",
+                ".^........#.",
+                "        ...#..v.",
+                "        .....#v.",
+                "        .#v<<<<.",
+                "        ..v...#.",
+            ]
+        );
+
+        let transitions: Vec<_> = trace.transitions.iter().map(|t| t.faces).collect();
+        assert_eq!(transitions, vec![(1, 4), (4, 6), (6, 5), (5, 2), (2, 3)]);
+        assert_eq!(
+            trace.transitions[1].to_string(),
+            "face 4 at (5, 11) facing East -> face 6 at (8, 14) facing South"
+        );
+    }
+
+    #[test]
+    fn cube_test() {
+        let lines = utils::load_input("inputs/day_22.example").expect("could not load input");
+        let parsed = parse_input(&lines).expect("could not parse input");
+        let cube = parsed.cube.expect("could not fold the cube");
+        assert_eq!(cube.size, 4);
+
+        // walking east from A lands on B facing south, as in the puzzle
+        assert_eq!(
+            cube.wrap(Coordinate(5, 11), Direction4::East),
+            (Coordinate(8, 14), Direction4::South)
+        );
+
+        // every edge is stitched both ways
+        for (&(i, dir), &(j, entering)) in &cube.edges {
+            assert_eq!(cube.edges[&(j, entering.reverse())], (i, dir.reverse()));
+        }
+
+        // a strip of 6 faces covers the right area but can't be folded
+        let strip: Grid<Tile> = vec![vec![Tile::Wall; 6]].into();
+        assert!(Cube::fold(&strip).is_none());
+
+        // which still works as a flat map, but has no answer for part two
+        let lines = utils::split_lines("......\n\n1");
+        let parsed = parse_input(&lines).expect("could not parse input");
+        assert_eq!(part_one(&parsed), 1008);
+        assert_eq!(part_two(&parsed), None);
+        assert_eq!(Day22::part_two(&parsed).to_string(), "unreachable");
     }
 }
@@ -1,43 +1,35 @@
-use anyhow::{Error, Result};
+use anyhow::Result;
 use rustc_hash::{FxHashMap, FxHashSet};
 
-use crate::grid::Coordinate;
+use crate::grid::{Coordinate, Direction4, Direction8, SparseGrid, Turn};
+use crate::solution::Day;
+use crate::utils::ParseError;
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
-enum Direction {
-    North,
-    South,
-    West,
-    East,
-}
+/// The order in which the elves consider the directions, which starts one later every round
+const ORDER: [Direction4; 4] = [
+    Direction4::North,
+    Direction4::South,
+    Direction4::West,
+    Direction4::East,
+];
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 struct StartingDirection {
-    directions: [Direction; 4],
     index: usize,
 }
 
 impl StartingDirection {
     pub fn new() -> Self {
-        Self {
-            directions: [
-                Direction::North,
-                Direction::South,
-                Direction::West,
-                Direction::East,
-            ],
-            index: 0,
-        }
+        Self { index: 0 }
     }
 }
 
 impl Iterator for StartingDirection {
-    type Item = Direction;
+    type Item = usize;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.index %= self.directions.len();
-        let ret = self.directions[self.index];
-        self.index += 1;
+        let ret = self.index;
+        self.index = (self.index + 1) % ORDER.len();
         Some(ret)
     }
 }
@@ -45,43 +37,15 @@ impl Iterator for StartingDirection {
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 struct Proposal {
     position: Coordinate,
-    starting_dir: Direction,
-    directions: [Direction; 4],
+    start: usize,
     index: usize,
 }
 
 impl Proposal {
-    pub fn new(position: Coordinate, starting_dir: Direction) -> Self {
-        let directions = match starting_dir {
-            Direction::North => [
-                Direction::North,
-                Direction::South,
-                Direction::West,
-                Direction::East,
-            ],
-            Direction::South => [
-                Direction::South,
-                Direction::West,
-                Direction::East,
-                Direction::North,
-            ],
-            Direction::West => [
-                Direction::West,
-                Direction::East,
-                Direction::North,
-                Direction::South,
-            ],
-            Direction::East => [
-                Direction::East,
-                Direction::North,
-                Direction::South,
-                Direction::West,
-            ],
-        };
+    pub fn new(position: Coordinate, start: usize) -> Self {
         Self {
             position,
-            starting_dir,
-            directions,
+            start,
             index: 0,
         }
     }
@@ -91,32 +55,17 @@ impl Iterator for Proposal {
     type Item = [Coordinate; 3];
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.index >= self.directions.len() {
+        if self.index >= ORDER.len() {
             return None;
         }
 
-        let ret = match self.directions[self.index] {
-            Direction::North => [
-                self.position.north(),
-                self.position.northeast(),
-                self.position.northwest(),
-            ],
-            Direction::South => [
-                self.position.south(),
-                self.position.southeast(),
-                self.position.southwest(),
-            ],
-            Direction::West => [
-                self.position.west(),
-                self.position.northwest(),
-                self.position.southwest(),
-            ],
-            Direction::East => [
-                self.position.east(),
-                self.position.northeast(),
-                self.position.southeast(),
-            ],
-        };
+        // the cell in the direction itself, followed by the 2 diagonals on either side of it
+        let dir = Direction8::from(ORDER[(self.start + self.index) % ORDER.len()]);
+        let ret = [
+            self.position.step(dir, 1),
+            self.position.step(dir.turn(Turn::Clockwise), 1),
+            self.position.step(dir.turn(Turn::Counterclockwise), 1),
+        ];
 
         self.index += 1;
         Some(ret)
@@ -125,7 +74,7 @@ impl Iterator for Proposal {
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Grove {
-    elves: FxHashSet<Coordinate>,
+    elves: SparseGrid<bool>,
     starting_dir: StartingDirection,
     proposed: FxHashMap<Coordinate, Coordinate>,
     seen: FxHashSet<Coordinate>,
@@ -138,10 +87,10 @@ impl Grove {
             .next()
             .expect("could not get starting dir");
 
-        for coord in &self.elves {
-            if coord.neighbours().iter().any(|n| self.elves.contains(n)) {
-                for coords in Proposal::new(*coord, starting_dir) {
-                    if coords.iter().any(|c| self.elves.contains(c)) {
+        for (coord, _) in self.elves.iter() {
+            if self.elves.neighbours(coord).next().is_some() {
+                for coords in Proposal::new(coord, starting_dir) {
+                    if coords.iter().any(|c| self.elves.contains(*c)) {
                         continue;
                     }
 
@@ -151,7 +100,7 @@ impl Grove {
                     }
 
                     self.seen.insert(coords[0]);
-                    self.proposed.insert(coords[0], *coord);
+                    self.proposed.insert(coords[0], coord);
                     break;
                 }
             }
@@ -160,8 +109,8 @@ impl Grove {
 
     fn execute(&mut self) {
         for (to, from) in self.proposed.drain() {
-            self.elves.remove(&from);
-            self.elves.insert(to);
+            self.elves.remove(from);
+            self.elves.insert(to, true);
         }
         self.seen.clear();
     }
@@ -191,32 +140,30 @@ impl Grove {
     }
 
     fn count_empty(&self) -> usize {
-        let mut min_row = isize::MAX;
-        let mut min_col = isize::MAX;
-        let mut max_row = isize::MIN;
-        let mut max_col = isize::MIN;
-
-        for coord in &self.elves {
-            min_row = min_row.min(coord.row());
-            min_col = min_col.min(coord.col());
-            max_row = max_row.max(coord.row());
-            max_col = max_col.max(coord.col());
-        }
-
-        (max_row - min_row + 1) as usize * (max_col - min_col + 1) as usize - self.elves.len()
+        let (min, max) = self.elves.bounds().unwrap_or_default();
+        (max.row() - min.row() + 1) as usize * (max.col() - min.col() + 1) as usize
+            - self.elves.len()
     }
 }
 
 impl TryFrom<&[String]> for Grove {
-    type Error = Error;
+    type Error = ParseError;
 
     fn try_from(lines: &[String]) -> Result<Self, Self::Error> {
-        let mut elves = FxHashSet::default();
+        let mut elves = SparseGrid::default();
 
         for (i, l) in lines.iter().enumerate() {
             for (j, c) in l.char_indices() {
-                if c == '#' {
-                    elves.insert((i, j).into());
+                match c {
+                    '#' => {
+                        elves.insert((i, j).into(), true);
+                    }
+                    '.' => {}
+                    _ => {
+                        return Err(ParseError::new("an elf `#` or ground `.`")
+                            .at_line(i)
+                            .at_offset(j))
+                    }
                 }
             }
         }
@@ -231,7 +178,7 @@ impl TryFrom<&[String]> for Grove {
 }
 
 pub fn parse_input(lines: &[String]) -> Result<Grove> {
-    Grove::try_from(lines)
+    Ok(Grove::try_from(lines)?)
 }
 
 pub fn part_one(parsed: &Grove) -> usize {
@@ -245,6 +192,29 @@ pub fn part_two(parsed: &Grove) -> usize {
     grove.fully_disperse()
 }
 
+pub struct Day23;
+
+impl Day for Day23 {
+    const DAY: u8 = 23;
+    const TITLE: &'static str = "unstable diffusion";
+
+    type Parsed = Grove;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(lines: &[String]) -> Result<Self::Parsed> {
+        parse_input(lines)
+    }
+
+    fn part_one(parsed: &Self::Parsed) -> Self::PartOne {
+        part_one(parsed)
+    }
+
+    fn part_two(parsed: &Self::Parsed) -> Self::PartTwo {
+        part_two(parsed)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,331 @@
+use anyhow::Result;
+
+use crate::grid::{Coordinate, Direction4, Glyph, Grid};
+use crate::search;
+use crate::solution::{Day, Reachable};
+use crate::utils::ParseError;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum Tile {
+    Wall,
+    Open,
+    Blizzard(Direction4),
+}
+
+impl Glyph for Tile {
+    fn glyph(&self) -> char {
+        match self {
+            Self::Wall => '#',
+            Self::Open => '.',
+            Self::Blizzard(dir) => dir.glyph(),
+        }
+    }
+
+    fn from_glyph(c: char) -> Result<Self, ParseError> {
+        Ok(match c {
+            '#' => Self::Wall,
+            '.' => Self::Open,
+            '^' | 'v' | '<' | '>' => Self::Blizzard(Direction4::try_from(c)?),
+            _ => return Err(ParseError::new("a tile (#, ., ^, v, < or >)")),
+        })
+    }
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        return a;
+    }
+
+    gcd(b, a % b)
+}
+
+/// Rotates the lowest [len] bits of [bits] by [k] towards the higher bits
+fn rotate(bits: u128, k: usize, len: usize) -> u128 {
+    let k = k % len;
+    if k == 0 {
+        return bits;
+    }
+
+    let mask = u128::MAX >> (u128::BITS as usize - len);
+    ((bits << k) | (bits >> (len - k))) & mask
+}
+
+#[derive(Debug, Clone)]
+pub struct Basin {
+    grid: Grid<Tile>,
+    start: Coordinate,
+    goal: Coordinate,
+    /// For each minute of the cycle of the blizzards, the tiles of each row inside the walls that
+    /// are swept by a blizzard, as a bitset of columns
+    occupied: Vec<Vec<u128>>,
+}
+
+impl Basin {
+    /// The widest and tallest the inside of the basin can be, so that a row or a column fits in a
+    /// bitset
+    const MAX_SIZE: usize = u128::BITS as usize;
+
+    fn new(grid: Grid<Tile>, start: Coordinate, goal: Coordinate) -> Self {
+        let (height, width) = (grid.n - 2, grid.m - 2);
+
+        // blizzards never leave their row or column, so each row holds the ones going east or
+        // west and each column the ones going north or south
+        let mut east = vec![0; height];
+        let mut west = vec![0; height];
+        let mut north = vec![0; width];
+        let mut south = vec![0; width];
+
+        for (coord, tile) in grid.cells() {
+            let Tile::Blizzard(dir) = tile else { continue };
+            let (row, col) = (coord.row() as usize - 1, coord.col() as usize - 1);
+
+            match dir {
+                Direction4::East => east[row] |= 1 << col,
+                Direction4::West => west[row] |= 1 << col,
+                Direction4::South => south[col] |= 1 << row,
+                Direction4::North => north[col] |= 1 << row,
+            }
+        }
+
+        // everything is back where it started once both the rows and the columns went around
+        let cycle = width / gcd(width, height) * height;
+
+        let occupied = (0..cycle)
+            .map(|minute| {
+                let mut rows: Vec<u128> = (0..height)
+                    .map(|row| {
+                        rotate(east[row], minute, width)
+                            | rotate(west[row], width - minute % width, width)
+                    })
+                    .collect();
+
+                for col in 0..width {
+                    let swept = rotate(south[col], minute, height)
+                        | rotate(north[col], height - minute % height, height);
+
+                    for (row, bits) in rows.iter_mut().enumerate() {
+                        *bits |= (swept >> row & 1) << col;
+                    }
+                }
+
+                rows
+            })
+            .collect();
+
+        Self {
+            grid,
+            start,
+            goal,
+            occupied,
+        }
+    }
+
+    /// Returns `true` if we can stand on [coord] at [minute] of the cycle of the blizzards
+    fn is_free(&self, coord: Coordinate, minute: usize) -> bool {
+        match self.grid.get(coord) {
+            None | Some(Tile::Wall) => false,
+            // the start and the goal are in the walls, where blizzards never go
+            _ if coord == self.start || coord == self.goal => true,
+            _ => {
+                let (row, col) = (coord.row() as usize - 1, coord.col() as usize - 1);
+                self.occupied[minute][row] >> col & 1 == 0
+            }
+        }
+    }
+
+    /// Returns the number of minutes it takes to get from [from] to [to], leaving at [minute]
+    fn crossing(&self, from: Coordinate, to: Coordinate, minute: usize) -> Option<usize> {
+        let cycle = self.occupied.len();
+
+        // the blizzards repeat, so we only need to know where we are in their cycle
+        let path = search::bfs(
+            [(from, minute % cycle)],
+            |&(coord, minute)| {
+                let next = (minute + 1) % cycle;
+
+                [coord]
+                    .into_iter()
+                    .chain(Direction4::ALL.map(|dir| coord.step(dir, 1)))
+                    .filter(move |&c| self.is_free(c, next))
+                    .map(move |c| (c, next))
+            },
+            |&(coord, _)| coord == to,
+        )?;
+
+        Some(path.cost)
+    }
+
+    /// Returns the number of minutes it takes to make each of the [trips] one after the other,
+    /// alternating between the start and the goal
+    fn trips(&self, trips: usize) -> Option<usize> {
+        let mut minute = 0;
+        let (mut from, mut to) = (self.start, self.goal);
+
+        for _ in 0..trips {
+            minute += self.crossing(from, to, minute)?;
+            (from, to) = (to, from);
+        }
+
+        Some(minute)
+    }
+}
+
+impl TryFrom<&[String]> for Basin {
+    type Error = ParseError;
+
+    fn try_from(lines: &[String]) -> Result<Self, Self::Error> {
+        let grid: Grid<Tile> = lines.try_into()?;
+
+        if !(3..Self::MAX_SIZE + 3).contains(&grid.n) {
+            return Err(
+                ParseError::new(format!("between 3 and {} rows", Self::MAX_SIZE + 2)).at_line(0),
+            );
+        }
+
+        if !(3..Self::MAX_SIZE + 3).contains(&grid.m) {
+            return Err(
+                ParseError::new(format!("between 3 and {} columns", Self::MAX_SIZE + 2)).at_line(0),
+            );
+        }
+
+        // the start and the goal are the only gaps in the top and the bottom walls
+        let opening = |row: usize| {
+            let mut open = (0..grid.m).filter(|&col| grid.row(row)[col] != Tile::Wall);
+
+            match (open.next(), open.next()) {
+                (Some(col), None) => Ok(Coordinate::from((row, col))),
+                _ => Err(ParseError::new("a wall with a single gap").at_line(row)),
+            }
+        };
+        let start = opening(0)?;
+        let goal = opening(grid.n - 1)?;
+
+        // apart from those gaps the border is all walls, and there are no walls inside, so that
+        // blizzards always stay inside and wrap around
+        for (coord, &tile) in grid.cells() {
+            let expected = if coord == start || coord == goal {
+                (tile != Tile::Open).then_some("an open tile")
+            } else if grid.is_on_edge(coord) {
+                (tile != Tile::Wall).then_some("a wall")
+            } else {
+                (tile == Tile::Wall).then_some("an open tile or a blizzard")
+            };
+
+            if let Some(expected) = expected {
+                return Err(ParseError::new(expected)
+                    .at_line(coord.row() as usize)
+                    .at_offset(coord.col() as usize));
+            }
+        }
+
+        Ok(Self::new(grid, start, goal))
+    }
+}
+
+pub fn parse_input(lines: &[String]) -> Result<Basin> {
+    Ok(Basin::try_from(lines)?)
+}
+
+/// Returns the fewest minutes to reach the goal, or `None` if the blizzards never let us through
+pub fn part_one(parsed: &Basin) -> Option<usize> {
+    parsed.trips(1)
+}
+
+/// Returns the fewest minutes to reach the goal, go back for the snacks and reach the goal again
+pub fn part_two(parsed: &Basin) -> Option<usize> {
+    parsed.trips(3)
+}
+
+pub struct Day24;
+
+impl Day for Day24 {
+    const DAY: u8 = 24;
+    const TITLE: &'static str = "blizzard basin";
+
+    type Parsed = Basin;
+    type PartOne = Reachable<usize>;
+    type PartTwo = Reachable<usize>;
+
+    fn parse(lines: &[String]) -> Result<Self::Parsed> {
+        parse_input(lines)
+    }
+
+    fn part_one(parsed: &Self::Parsed) -> Self::PartOne {
+        part_one(parsed).into()
+    }
+
+    fn part_two(parsed: &Self::Parsed) -> Self::PartTwo {
+        part_two(parsed).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils;
+
+    #[test]
+    fn part_one_test() {
+        let lines = utils::load_input("inputs/day_24.example").expect("could not load input");
+        let parsed = parse_input(&lines).expect("could not parse input");
+        assert_eq!(part_one(&parsed), Some(18));
+    }
+
+    #[test]
+    fn part_two_test() {
+        let lines = utils::load_input("inputs/day_24.example").expect("could not load input");
+        let parsed = parse_input(&lines).expect("could not parse input");
+        assert_eq!(part_two(&parsed), Some(54));
+    }
+
+    #[test]
+    fn blizzard_test() {
+        let lines = utils::load_input("inputs/day_24.example").expect("could not load input");
+        let parsed = parse_input(&lines).expect("could not parse input");
+
+        // the 4x6 inside repeats every 12 minutes
+        assert_eq!(parsed.occupied.len(), 12);
+        assert_eq!(parsed.start, Coordinate(0, 1));
+        assert_eq!(parsed.goal, Coordinate(5, 6));
+
+        // the first row after 1 minute, as drawn in the puzzle: #.>3.<.#
+        assert_eq!(parsed.occupied[1][0], 0b010110);
+        assert!(parsed.is_free(Coordinate(1, 1), 1));
+        assert!(!parsed.is_free(Coordinate(1, 2), 1));
+        assert!(!parsed.is_free(Coordinate(0, 0), 1));
+
+        assert_eq!(rotate(0b0011, 1, 4), 0b0110);
+        assert_eq!(rotate(0b1001, 1, 4), 0b0011);
+        assert_eq!(rotate(0b1001, 3, 4), 0b1100);
+        assert_eq!(rotate(1 << 127, 1, 128), 1);
+
+        let lines = utils::split_lines("#.##\n#..#\n#..#");
+        let err = parse_input(&lines).expect_err("should not parse");
+        assert!(err.to_string().contains("a wall with a single gap"));
+    }
+
+    #[test]
+    fn border_test() {
+        let invalid = [
+            // a blizzard in the top wall, in place of or next to the gap
+            ("#>####\n#....#\n####.#", "an open tile"),
+            ("#.>###\n#....#\n####.#", "a wall with a single gap"),
+            // a gap in a side wall
+            ("#.####\n.....#\n####.#", "a wall"),
+            // a wall inside
+            ("#.####\n#.#..#\n####.#", "an open tile or a blizzard"),
+        ];
+
+        for (input, expected) in invalid {
+            let lines = utils::split_lines(input);
+            let err = parse_input(&lines).expect_err("should not parse");
+            assert!(err.to_string().contains(expected), "{}", err);
+        }
+
+        // the blizzards always block the only way down
+        let lines = utils::split_lines("#.#\n#v#\n#.#");
+        let parsed = parse_input(&lines).expect("could not parse input");
+        assert_eq!(part_one(&parsed), None);
+        assert_eq!(Day24::part_two(&parsed).to_string(), "unreachable");
+    }
+}
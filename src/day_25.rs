@@ -1,19 +1,22 @@
-use anyhow::{bail, Error, Result};
+use anyhow::Result;
 use std::fmt;
 use std::iter::Sum;
 use std::ops::Add;
 use std::str::FromStr;
 
+use crate::solution::Day;
+use crate::utils::{self, ParseError};
+
 #[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
 struct Snafu(i64);
 
 impl FromStr for Snafu {
-    type Err = Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut number = 0;
 
-        for c in s.chars() {
+        for (i, c) in s.char_indices() {
             number *= 5;
 
             let digit = match c {
@@ -22,7 +25,7 @@ impl FromStr for Snafu {
                 '0' => 0,
                 '1' => 1,
                 '2' => 2,
-                _ => bail!("Invalid SNAFU number"),
+                _ => return Err(ParseError::new("a SNAFU digit (=, -, 0, 1 or 2)").at_offset(i)),
             };
 
             number += digit;
@@ -83,20 +86,17 @@ impl Bob {
 }
 
 impl TryFrom<&[String]> for Bob {
-    type Error = Error;
+    type Error = ParseError;
 
     fn try_from(lines: &[String]) -> Result<Self, Self::Error> {
         Ok(Bob {
-            fuel_requirements: lines
-                .iter()
-                .map(|l| Snafu::from_str(l))
-                .collect::<Result<Vec<_>, _>>()?,
+            fuel_requirements: utils::parse_lines(lines)?,
         })
     }
 }
 
 pub fn parse_input(lines: &[String]) -> Result<Bob> {
-    lines.try_into()
+    Ok(lines.try_into()?)
 }
 
 pub fn part_one(parsed: &Bob) -> String {
@@ -107,6 +107,29 @@ pub fn part_two(_: &Bob) -> usize {
     0
 }
 
+pub struct Day25;
+
+impl Day for Day25 {
+    const DAY: u8 = 25;
+    const TITLE: &'static str = "full of hot air";
+
+    type Parsed = Bob;
+    type PartOne = String;
+    type PartTwo = usize;
+
+    fn parse(lines: &[String]) -> Result<Self::Parsed> {
+        parse_input(lines)
+    }
+
+    fn part_one(parsed: &Self::Parsed) -> Self::PartOne {
+        part_one(parsed)
+    }
+
+    fn part_two(parsed: &Self::Parsed) -> Self::PartTwo {
+        part_two(parsed)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
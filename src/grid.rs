@@ -1,8 +1,16 @@
 use std::{
     convert::TryFrom,
-    ops::{Index, IndexMut},
+    fmt,
+    ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Sub, SubAssign},
+    str::FromStr,
+    sync::OnceLock,
 };
 
+use rustc_hash::FxHashMap;
+
+use crate::algebra::{Point3, Vector2};
+use crate::utils::ParseError;
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
 pub struct Coordinate(pub isize, pub isize);
 
@@ -99,54 +107,568 @@ impl Coordinate {
     pub fn manhattan_distance(&self, other: &Self) -> usize {
         other.0.abs_diff(self.0) + other.1.abs_diff(self.1)
     }
+
+    /// The number of king moves between 2 coordinates, where diagonals count as 1 step
+    pub fn chebyshev_distance(&self, other: &Self) -> usize {
+        other.0.abs_diff(self.0).max(other.1.abs_diff(self.1))
+    }
+
+    pub fn euclidean_distance(&self, other: &Self) -> f64 {
+        let (di, dj) = ((other.0 - self.0) as f64, (other.1 - self.1) as f64);
+        di.hypot(dj)
+    }
+
+    /// Moves [n] cells in [dir]
+    pub fn step(&self, dir: impl Into<Direction8>, n: usize) -> Self {
+        *self + dir.into().offset() * n as isize
+    }
+
+    /// Rotates by 90° clockwise around the origin, as seen on screen with rows going down
+    pub fn rotate_clockwise(&self) -> Self {
+        Self(self.1, -self.0)
+    }
+
+    /// Rotates by 90° counterclockwise around the origin, as seen on screen with rows going down
+    pub fn rotate_counterclockwise(&self) -> Self {
+        Self(-self.1, self.0)
+    }
+}
+
+impl Add for Coordinate {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self(self.0 + rhs.0, self.1 + rhs.1)
+    }
+}
+
+impl Sub for Coordinate {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self(self.0 - rhs.0, self.1 - rhs.1)
+    }
+}
+
+impl AddAssign for Coordinate {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for Coordinate {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Neg for Coordinate {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self(-self.0, -self.1)
+    }
+}
+
+impl Mul<isize> for Coordinate {
+    type Output = Self;
+
+    fn mul(self, rhs: isize) -> Self::Output {
+        Self(self.0 * rhs, self.1 * rhs)
+    }
+}
+
+/// Converts to x and y, with y pointing up
+impl From<Coordinate> for Vector2<isize> {
+    fn from(c: Coordinate) -> Self {
+        Vector2::new(c.x(), c.y())
+    }
+}
+
+impl From<Vector2<isize>> for Coordinate {
+    fn from(v: Vector2<isize>) -> Self {
+        Coordinate(-v.y, v.x)
+    }
+}
+
+/// Places the coordinate on the z = 0 plane, using [Coordinate::x] and [Coordinate::y]
+impl From<Coordinate> for Point3 {
+    fn from(c: Coordinate) -> Self {
+        Point3::new(c.x() as i64, c.y() as i64, 0)
+    }
+}
+
+impl Point3 {
+    /// Projects the point onto the z = 0 plane, the inverse of `Point3::from(Coordinate)`
+    pub fn to_coordinate(&self) -> Coordinate {
+        Coordinate(-self.y as isize, self.x as isize)
+    }
+}
+
+/// A 90° turn to either side
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Turn {
+    Clockwise,
+    Counterclockwise,
+}
+
+impl Turn {
+    pub fn reverse(&self) -> Self {
+        match self {
+            Self::Clockwise => Self::Counterclockwise,
+            Self::Counterclockwise => Self::Clockwise,
+        }
+    }
+}
+
+impl TryFrom<char> for Turn {
+    type Error = ParseError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        Ok(match value {
+            'R' => Self::Clockwise,
+            'L' => Self::Counterclockwise,
+            _ => return Err(ParseError::new("a turn (R or L)")),
+        })
+    }
+}
+
+/// One of the 4 cardinal directions, with north pointing to the previous row
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Direction4 {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction4 {
+    /// All the directions in clockwise order, starting north
+    pub const ALL: [Self; 4] = [Self::North, Self::East, Self::South, Self::West];
+
+    fn from_index(i: usize) -> Self {
+        Self::ALL[i % 4]
+    }
+
+    pub fn turn(&self, turn: Turn) -> Self {
+        match turn {
+            Turn::Clockwise => Self::from_index(*self as usize + 1),
+            Turn::Counterclockwise => Self::from_index(*self as usize + 3),
+        }
+    }
+
+    pub fn reverse(&self) -> Self {
+        Self::from_index(*self as usize + 2)
+    }
+
+    /// The coordinate of a 1 cell step in this direction from the origin
+    pub fn offset(&self) -> Coordinate {
+        Direction8::from(*self).offset()
+    }
+
+    /// Returns the direction of a 1 cell step with the given [offset], if there is one
+    pub fn from_offset(offset: Coordinate) -> Option<Self> {
+        Self::ALL.into_iter().find(|d| d.offset() == offset)
+    }
 }
 
+/// Parses `U/D/L/R`, `N/S/E/W` and `^v<>`
+impl TryFrom<char> for Direction4 {
+    type Error = ParseError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        Ok(match value {
+            'U' | 'N' | '^' => Self::North,
+            'R' | 'E' | '>' => Self::East,
+            'D' | 'S' | 'v' => Self::South,
+            'L' | 'W' | '<' => Self::West,
+            _ => return Err(ParseError::new("a direction (U/D/L/R, N/S/E/W or ^v<>)")),
+        })
+    }
+}
+
+impl FromStr for Direction4 {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Self::try_from(c),
+            _ => Err(ParseError::new("a direction (U/D/L/R, N/S/E/W or ^v<>)")),
+        }
+    }
+}
+
+/// Drawn as an arrow
+impl Glyph for Direction4 {
+    fn glyph(&self) -> char {
+        match self {
+            Self::North => '^',
+            Self::East => '>',
+            Self::South => 'v',
+            Self::West => '<',
+        }
+    }
+
+    fn from_glyph(c: char) -> Result<Self, ParseError> {
+        Self::try_from(c)
+    }
+}
+
+/// One of the 4 cardinal or 4 ordinal directions
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Direction8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction8 {
+    /// All the directions in clockwise order, starting north
+    pub const ALL: [Self; 8] = [
+        Self::North,
+        Self::NorthEast,
+        Self::East,
+        Self::SouthEast,
+        Self::South,
+        Self::SouthWest,
+        Self::West,
+        Self::NorthWest,
+    ];
+
+    fn from_index(i: usize) -> Self {
+        Self::ALL[i % 8]
+    }
+
+    /// Turns by 45° to the given side
+    pub fn turn(&self, turn: Turn) -> Self {
+        match turn {
+            Turn::Clockwise => Self::from_index(*self as usize + 1),
+            Turn::Counterclockwise => Self::from_index(*self as usize + 7),
+        }
+    }
+
+    pub fn reverse(&self) -> Self {
+        Self::from_index(*self as usize + 4)
+    }
+
+    /// The coordinate of a 1 cell step in this direction from the origin
+    pub fn offset(&self) -> Coordinate {
+        match self {
+            Self::North => Coordinate(-1, 0),
+            Self::NorthEast => Coordinate(-1, 1),
+            Self::East => Coordinate(0, 1),
+            Self::SouthEast => Coordinate(1, 1),
+            Self::South => Coordinate(1, 0),
+            Self::SouthWest => Coordinate(1, -1),
+            Self::West => Coordinate(0, -1),
+            Self::NorthWest => Coordinate(-1, -1),
+        }
+    }
+}
+
+impl From<Direction4> for Direction8 {
+    fn from(dir: Direction4) -> Self {
+        Self::from_index(dir as usize * 2)
+    }
+}
+
+/// Parses the compass forms `N`, `NE`, `E`, ... as well as any form of [Direction4]
+impl FromStr for Direction8 {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "NE" => Self::NorthEast,
+            "SE" => Self::SouthEast,
+            "SW" => Self::SouthWest,
+            "NW" => Self::NorthWest,
+            _ => Direction4::from_str(s)
+                .map_err(|_| ParseError::new("a direction (N, NE, E, SE, S, SW, W or NW)"))?
+                .into(),
+        })
+    }
+}
+
+/// A cell that is drawn as, and parsed from, a single character
+pub trait Glyph: Sized {
+    fn glyph(&self) -> char;
+
+    fn from_glyph(c: char) -> Result<Self, ParseError>;
+}
+
+impl Glyph for char {
+    fn glyph(&self) -> char {
+        *self
+    }
+
+    fn from_glyph(c: char) -> Result<Self, ParseError> {
+        Ok(c)
+    }
+}
+
+/// `#` for true and `.` for false, like the puzzle descriptions draw them
+impl Glyph for bool {
+    fn glyph(&self) -> char {
+        if *self {
+            '#'
+        } else {
+            '.'
+        }
+    }
+
+    fn from_glyph(c: char) -> Result<Self, ParseError> {
+        match c {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _ => Err(ParseError::new("`#` or `.`")),
+        }
+    }
+}
+
+/// A single decimal digit, which unlike a plain `u8` always fits in one character, so a grid of
+/// them parses back from how it is drawn
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Digit(u8);
+
+impl Digit {
+    /// Returns `None` if [value] is above 9
+    pub fn new(value: u8) -> Option<Self> {
+        (value <= 9).then_some(Self(value))
+    }
+
+    pub fn get(self) -> u8 {
+        self.0
+    }
+}
+
+impl Glyph for Digit {
+    fn glyph(&self) -> char {
+        char::from(b'0' + self.0)
+    }
+
+    fn from_glyph(c: char) -> Result<Self, ParseError> {
+        c.to_digit(10)
+            .map(|d| Self(d as u8))
+            .ok_or_else(|| ParseError::new("a digit"))
+    }
+}
+
+/// A fixed size grid of [n] rows by [m] columns, stored row after row in a single vector
 #[derive(Debug, Clone)]
 pub struct Grid<T> {
-    pub grid: Vec<Vec<T>>,
+    cells: Vec<T>,
     pub n: usize,
     pub m: usize,
 }
 
-impl<T> TryFrom<&[String]> for Grid<T>
-where
-    T: TryFrom<char>,
-{
-    type Error = T::Error;
+impl<T: Glyph> TryFrom<&[String]> for Grid<T> {
+    type Error = ParseError;
 
     fn try_from(lines: &[String]) -> Result<Self, Self::Error> {
-        let grid = lines
-            .iter()
-            .map(|line| {
-                line.chars()
-                    .map(|c| T::try_from(c))
-                    .collect::<Result<Vec<T>, T::Error>>()
-            })
-            .collect::<Result<Vec<Vec<T>>, T::Error>>()?;
+        let width = lines
+            .first()
+            .map(|line| line.chars().count())
+            .filter(|&width| width > 0)
+            .ok_or_else(|| ParseError::new("a non-empty grid").at_line(0))?;
+
+        let mut cells = Vec::with_capacity(lines.len() * width);
 
-        Ok(grid.into())
+        for (i, line) in lines.iter().enumerate() {
+            for (j, c) in line.char_indices() {
+                cells.push(T::from_glyph(c).map_err(|e| e.at_line(i).at_offset(j))?);
+            }
+
+            if cells.len() != (i + 1) * width {
+                return Err(ParseError::new(format!("a row of {} cells", width))
+                    .at_line(i)
+                    .at_offset(line.len()));
+            }
+        }
+
+        Ok(Self::from_cells(lines.len(), width, cells))
     }
 }
 
+/// Every row must have the same length
 impl<T> From<Vec<Vec<T>>> for Grid<T> {
     fn from(grid: Vec<Vec<T>>) -> Self {
         let n = grid.len();
-        let m = grid[0].len();
-        Self { grid, n, m }
+        let m = grid.first().map_or(0, |row| row.len());
+        assert!(
+            grid.iter().all(|row| row.len() == m),
+            "every row must have {} cells",
+            m
+        );
+
+        Self::from_cells(n, m, grid.into_iter().flatten().collect())
     }
 }
 
+impl<T> Grid<T> {
+    /// Builds a grid of [n] rows by [m] columns from its [cells], listed row after row
+    pub fn from_cells(n: usize, m: usize, cells: Vec<T>) -> Self {
+        assert_eq!(
+            cells.len(),
+            n * m,
+            "a {}x{} grid needs {} cells",
+            n,
+            m,
+            n * m
+        );
+        Self { cells, n, m }
+    }
+
+    pub fn get(&self, coord: Coordinate) -> Option<&T> {
+        if !(0..self.n as isize).contains(&coord.0) || !(0..self.m as isize).contains(&coord.1) {
+            return None;
+        }
+
+        Some(&self.row(coord.0 as usize)[coord.1 as usize])
+    }
+
+    pub fn row(&self, i: usize) -> &[T] {
+        &self.cells[i * self.m..(i + 1) * self.m]
+    }
+
+    pub fn row_mut(&mut self, i: usize) -> &mut [T] {
+        &mut self.cells[i * self.m..(i + 1) * self.m]
+    }
+
+    /// Iterates over the cells of the [j]th column, top to bottom
+    pub fn col(&self, j: usize) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator {
+        assert!(j < self.m, "column {} is out of bounds", j);
+        self.cells[j..].iter().step_by(self.m)
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> + ExactSizeIterator {
+        // chunks_exact would yield nothing at all for a grid without columns
+        (0..self.n).map(|i| self.row(i))
+    }
+
+    pub fn cols(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = impl DoubleEndedIterator<Item = &T> + ExactSizeIterator>
+           + ExactSizeIterator {
+        (0..self.m).map(|j| self.col(j))
+    }
+
+    /// Iterates over every cell together with its coordinate, row by row
+    pub fn cells(&self) -> impl Iterator<Item = (Coordinate, &T)> {
+        let m = self.m;
+
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(k, value)| ((k / m, k % m).into(), value))
+    }
+
+    /// Iterates over the coordinates of every cell satisfying [pred], row by row
+    pub fn find_all<'a>(
+        &'a self,
+        pred: impl Fn(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = Coordinate> + 'a {
+        self.cells()
+            .filter(move |(_, value)| pred(value))
+            .map(|(coord, _)| coord)
+    }
+
+    /// Returns the neighbours of [coord] among all 8 that are inside the grid
+    pub fn neighbours(&self, coord: Coordinate) -> impl Iterator<Item = Coordinate> + '_ {
+        self.clip(coord.neighbours())
+    }
+
+    /// Returns the neighbours of [coord] among the 4 cardinal ones that are inside the grid
+    pub fn cardinal_neighbours(&self, coord: Coordinate) -> impl Iterator<Item = Coordinate> + '_ {
+        self.clip(coord.cardinal_neighbours())
+    }
+
+    fn clip<const N: usize>(
+        &self,
+        coords: [Coordinate; N],
+    ) -> impl Iterator<Item = Coordinate> + '_ {
+        coords.into_iter().filter(|&c| self.get(c).is_some())
+    }
+
+    /// Builds a grid of the same size by applying [f] to every cell
+    pub fn map<U>(&self, mut f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(&mut f).collect(),
+            n: self.n,
+            m: self.m,
+        }
+    }
+
+    /// Returns the [n] by [m] window of the grid whose top left corner is at [origin], or `None`
+    /// if the window does not fit in the grid
+    pub fn view(&self, origin: Coordinate, n: usize, m: usize) -> Option<GridView<'_, T>> {
+        let fits = origin.0 >= 0
+            && origin.1 >= 0
+            && origin.0 as usize + n <= self.n
+            && origin.1 as usize + m <= self.m;
+
+        fits.then_some(GridView {
+            grid: self,
+            origin,
+            n,
+            m,
+        })
+    }
+
+    /// Draws the grid with one line per row, using [glyph] for each cell
+    pub fn render(&self, glyph: impl Fn(&T) -> char) -> String {
+        self.render_with(glyph, [])
+    }
+
+    /// Like [Grid::render], but the cells in [overlay] are drawn with the given character instead,
+    /// which is handy for highlighting a path or a position
+    pub fn render_with(
+        &self,
+        glyph: impl Fn(&T) -> char,
+        overlay: impl IntoIterator<Item = (Coordinate, char)>,
+    ) -> String {
+        let mut chars = self.map(glyph);
+
+        for (coord, c) in overlay {
+            if chars.get(coord).is_some() {
+                chars[coord] = c;
+            }
+        }
+
+        chars
+            .rows()
+            .map(|row| row.iter().collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Parsing the output with [TryFrom] gives back the same grid
+impl<T: Glyph> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.render(T::glyph))
+    }
+}
+
+/// Indexing goes through the row first, so a column past the end panics instead of wrapping
+/// around into the next row
 impl<T> Index<Coordinate> for Grid<T> {
     type Output = T;
 
     fn index(&self, idx: Coordinate) -> &Self::Output {
-        &self.grid[idx.0 as usize][idx.1 as usize]
+        &self.row(idx.0 as usize)[idx.1 as usize]
     }
 }
 
 impl<T> IndexMut<Coordinate> for Grid<T> {
     fn index_mut(&mut self, idx: Coordinate) -> &mut Self::Output {
-        &mut self.grid[idx.0 as usize][idx.1 as usize]
+        &mut self.row_mut(idx.0 as usize)[idx.1 as usize]
     }
 }
 
@@ -155,11 +677,7 @@ where
     T: Copy + PartialEq,
 {
     pub fn new(n: usize, m: usize, default: T) -> Self {
-        Self {
-            grid: vec![vec![default; m]; n],
-            n,
-            m,
-        }
+        Self::from_cells(n, m, vec![default; n * m])
     }
 
     pub fn is_in_bounds(&self, coord: Coordinate) -> bool {
@@ -177,14 +695,648 @@ where
     }
 
     pub fn find_index(&self, pred: impl Fn(&T) -> bool) -> Option<Coordinate> {
-        for i in 0..self.n {
-            for j in 0..self.m {
-                if pred(&self.grid[i][j]) {
-                    return Some(Coordinate(i as isize, j as isize));
-                }
+        self.find_all(pred).next()
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Swaps rows and columns, so the cell at (i, j) ends up at (j, i)
+    pub fn transpose(&self) -> Self {
+        (0..self.m)
+            .map(|j| self.col(j).cloned().collect())
+            .collect::<Vec<Vec<T>>>()
+            .into()
+    }
+
+    pub fn rotate_clockwise(&self) -> Self {
+        self.transpose().flip_horizontal()
+    }
+
+    pub fn rotate_counterclockwise(&self) -> Self {
+        self.transpose().flip_vertical()
+    }
+
+    /// Mirrors the grid left to right
+    pub fn flip_horizontal(&self) -> Self {
+        self.rows()
+            .map(|row| row.iter().rev().cloned().collect())
+            .collect::<Vec<Vec<T>>>()
+            .into()
+    }
+
+    /// Mirrors the grid top to bottom
+    pub fn flip_vertical(&self) -> Self {
+        self.rows()
+            .rev()
+            .map(|row| row.to_vec())
+            .collect::<Vec<Vec<T>>>()
+            .into()
+    }
+}
+
+/// A rectangular window into a [Grid], indexed relative to its top left corner
+#[derive(Debug, Clone, Copy)]
+pub struct GridView<'a, T> {
+    grid: &'a Grid<T>,
+    origin: Coordinate,
+    pub n: usize,
+    pub m: usize,
+}
+
+impl<'a, T> GridView<'a, T> {
+    /// Returns where the top left corner of the view is in the grid
+    pub fn origin(&self) -> Coordinate {
+        self.origin
+    }
+
+    pub fn is_in_bounds(&self, coord: Coordinate) -> bool {
+        (0..self.n as isize).contains(&coord.0) && (0..self.m as isize).contains(&coord.1)
+    }
+
+    pub fn get(&self, coord: Coordinate) -> Option<&'a T> {
+        if !self.is_in_bounds(coord) {
+            return None;
+        }
+
+        self.grid.get(self.to_grid_coordinate(coord))
+    }
+
+    /// Converts a [coord] relative to the view into one relative to the grid
+    pub fn to_grid_coordinate(&self, coord: Coordinate) -> Coordinate {
+        coord + self.origin
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &'a [T]> + ExactSizeIterator {
+        let (i, j) = (self.origin.0 as usize, self.origin.1 as usize);
+        let m = self.m;
+
+        let grid = self.grid;
+        (i..i + self.n).map(move |i| &grid.row(i)[j..j + m])
+    }
+
+    /// Iterates over every cell together with its coordinate relative to the view, row by row
+    pub fn cells(&self) -> impl Iterator<Item = (Coordinate, &'a T)> {
+        self.rows().enumerate().flat_map(|(i, row)| {
+            row.iter()
+                .enumerate()
+                .map(move |(j, value)| ((i, j).into(), value))
+        })
+    }
+
+    /// Copies the view into a grid of its own
+    pub fn to_grid(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        self.rows()
+            .map(|row| row.to_vec())
+            .collect::<Vec<Vec<T>>>()
+            .into()
+    }
+}
+
+impl<T> Index<Coordinate> for GridView<'_, T> {
+    type Output = T;
+
+    fn index(&self, idx: Coordinate) -> &Self::Output {
+        self.get(idx)
+            .unwrap_or_else(|| panic!("{:?} is outside of the view", idx))
+    }
+}
+
+/// A fixed size 3D grid of [n] by [m] by [l] cells along x, y and z, indexed by [Point3]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Grid3<T> {
+    cells: Vec<T>,
+    pub n: usize,
+    pub m: usize,
+    pub l: usize,
+}
+
+impl<T> Grid3<T> {
+    pub fn is_in_bounds(&self, p: Point3) -> bool {
+        (0..self.n as i64).contains(&p.x)
+            && (0..self.m as i64).contains(&p.y)
+            && (0..self.l as i64).contains(&p.z)
+    }
+
+    /// Returns `true` if [p] is in bounds and on one of the 6 faces of the grid
+    pub fn is_on_edge(&self, p: Point3) -> bool {
+        self.is_in_bounds(p)
+            && (p.x == 0
+                || p.y == 0
+                || p.z == 0
+                || p.x == self.n as i64 - 1
+                || p.y == self.m as i64 - 1
+                || p.z == self.l as i64 - 1)
+    }
+
+    fn offset(&self, p: Point3) -> Option<usize> {
+        self.is_in_bounds(p)
+            .then(|| (p.x as usize * self.m + p.y as usize) * self.l + p.z as usize)
+    }
+
+    pub fn get(&self, p: Point3) -> Option<&T> {
+        self.offset(p).map(|k| &self.cells[k])
+    }
+
+    pub fn get_mut(&mut self, p: Point3) -> Option<&mut T> {
+        self.offset(p).map(|k| &mut self.cells[k])
+    }
+
+    /// Iterates over every cell together with its point, in x, then y, then z order
+    pub fn cells(&self) -> impl Iterator<Item = (Point3, &T)> {
+        let (m, l) = (self.m, self.l);
+
+        self.cells.iter().enumerate().map(move |(k, value)| {
+            let p = Point3::new((k / (m * l)) as i64, (k / l % m) as i64, (k % l) as i64);
+            (p, value)
+        })
+    }
+
+    /// Iterates over the points of every cell satisfying [pred]
+    pub fn find_all<'a>(
+        &'a self,
+        pred: impl Fn(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = Point3> + 'a {
+        self.cells()
+            .filter(move |(_, value)| pred(value))
+            .map(|(p, _)| p)
+    }
+
+    /// Returns the neighbours of [p] among the 6 cardinal ones that are inside the grid
+    pub fn cardinal_neighbours(&self, p: Point3) -> impl Iterator<Item = Point3> + '_ {
+        p.cardinal_neighbours()
+            .into_iter()
+            .filter(|&q| self.is_in_bounds(q))
+    }
+
+    /// Builds a grid of the same size by applying [f] to every cell
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid3<U> {
+        Grid3 {
+            cells: self.cells.iter().map(f).collect(),
+            n: self.n,
+            m: self.m,
+            l: self.l,
+        }
+    }
+}
+
+impl<T: Clone> Grid3<T> {
+    pub fn new(n: usize, m: usize, l: usize, default: T) -> Self {
+        Self {
+            cells: vec![default; n * m * l],
+            n,
+            m,
+            l,
+        }
+    }
+}
+
+impl<T> Index<Point3> for Grid3<T> {
+    type Output = T;
+
+    fn index(&self, idx: Point3) -> &Self::Output {
+        self.get(idx)
+            .unwrap_or_else(|| panic!("{:?} is out of bounds", idx))
+    }
+}
+
+impl<T> IndexMut<Point3> for Grid3<T> {
+    fn index_mut(&mut self, idx: Point3) -> &mut Self::Output {
+        self.get_mut(idx)
+            .unwrap_or_else(|| panic!("{:?} is out of bounds", idx))
+    }
+}
+
+/// A grid that only stores the cells that have been set, so it can grow in any direction,
+/// including into negative coordinates. Cells that were never set read as the default value.
+#[derive(Debug, Clone)]
+pub struct SparseGrid<T> {
+    cells: FxHashMap<Coordinate, T>,
+    default: T,
+    // kept up to date on insertion, and cleared when a cell on the edge of the bounding box is
+    // removed, which may shrink it, so that the next call to [bounds] recomputes it once
+    bounds: OnceLock<Option<(Coordinate, Coordinate)>>,
+}
+
+impl<T: PartialEq> PartialEq for SparseGrid<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cells == other.cells && self.default == other.default
+    }
+}
+
+impl<T: Eq> Eq for SparseGrid<T> {}
+
+impl<T: Default> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new(default: T) -> Self {
+        Self {
+            cells: FxHashMap::default(),
+            default,
+            bounds: OnceLock::from(None),
+        }
+    }
+
+    /// Returns the number of cells that are set
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, coord: Coordinate) -> bool {
+        self.cells.contains_key(&coord)
+    }
+
+    pub fn get(&self, coord: Coordinate) -> Option<&T> {
+        self.cells.get(&coord)
+    }
+
+    /// Sets the cell at [coord], returning its previous value if it was set
+    pub fn insert(&mut self, coord: Coordinate, value: T) -> Option<T> {
+        // a cleared box will include the new cell once it is recomputed
+        if let Some(bounds) = self.bounds.get_mut() {
+            *bounds = Some(match *bounds {
+                Some((min, max)) => (
+                    Coordinate(min.0.min(coord.0), min.1.min(coord.1)),
+                    Coordinate(max.0.max(coord.0), max.1.max(coord.1)),
+                ),
+                None => (coord, coord),
+            });
+        }
+
+        self.cells.insert(coord, value)
+    }
+
+    /// Unsets the cell at [coord], returning its value if it was set
+    pub fn remove(&mut self, coord: Coordinate) -> Option<T> {
+        let value = self.cells.remove(&coord)?;
+
+        if let Some(&Some((min, max))) = self.bounds.get() {
+            if coord.0 == min.0 || coord.0 == max.0 || coord.1 == min.1 || coord.1 == max.1 {
+                self.bounds.take();
             }
         }
 
-        None
+        Some(value)
+    }
+
+    /// Returns the top left and bottom right corners of the smallest box containing every cell
+    /// that is set, or `None` if no cell is set
+    pub fn bounds(&self) -> Option<(Coordinate, Coordinate)> {
+        *self.bounds.get_or_init(|| {
+            self.cells.keys().fold(None, |bounds, c| match bounds {
+                Some((min, max)) => Some((
+                    Coordinate(c.0.min(min.0), c.1.min(min.1)),
+                    Coordinate(c.0.max(max.0), c.1.max(max.1)),
+                )),
+                None => Some((*c, *c)),
+            })
+        })
+    }
+
+    /// Returns `true` if [coord] is inside the bounding box
+    pub fn is_in_bounds(&self, coord: Coordinate) -> bool {
+        self.bounds().is_some_and(|(min, max)| {
+            (min.0..=max.0).contains(&coord.0) && (min.1..=max.1).contains(&coord.1)
+        })
+    }
+
+    /// Iterates over the cells that are set, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (Coordinate, &T)> {
+        self.cells.iter().map(|(c, v)| (*c, v))
+    }
+
+    /// Returns the cells that are set among all 8 neighbours of [coord]
+    pub fn neighbours(&self, coord: Coordinate) -> impl Iterator<Item = (Coordinate, &T)> {
+        self.set_cells(coord.neighbours())
+    }
+
+    /// Returns the cells that are set among the 4 cardinal neighbours of [coord]
+    pub fn cardinal_neighbours(&self, coord: Coordinate) -> impl Iterator<Item = (Coordinate, &T)> {
+        self.set_cells(coord.cardinal_neighbours())
+    }
+
+    fn set_cells<const N: usize>(
+        &self,
+        coords: [Coordinate; N],
+    ) -> impl Iterator<Item = (Coordinate, &T)> {
+        coords
+            .into_iter()
+            .filter_map(|c| self.cells.get(&c).map(|v| (c, v)))
+    }
+}
+
+impl<T> Index<Coordinate> for SparseGrid<T> {
+    type Output = T;
+
+    fn index(&self, idx: Coordinate) -> &Self::Output {
+        self.cells.get(&idx).unwrap_or(&self.default)
+    }
+}
+
+impl<T: Clone> IndexMut<Coordinate> for SparseGrid<T> {
+    /// Sets the cell at [idx] to the default value first if it was not set yet
+    fn index_mut(&mut self, idx: Coordinate) -> &mut Self::Output {
+        if !self.cells.contains_key(&idx) {
+            self.insert(idx, self.default.clone());
+        }
+
+        self.cells.get_mut(&idx).expect("cell was just inserted")
+    }
+}
+
+impl<T> From<Grid<T>> for SparseGrid<T>
+where
+    T: Default + PartialEq,
+{
+    /// Only the cells that differ from the default value are set
+    fn from(grid: Grid<T>) -> Self {
+        let mut sparse = Self::default();
+
+        for (k, value) in grid.cells.into_iter().enumerate() {
+            if value != sparse.default {
+                sparse.insert((k / grid.m, k % grid.m).into(), value);
+            }
+        }
+
+        sparse
+    }
+}
+
+impl<T> From<&SparseGrid<T>> for Grid<T>
+where
+    T: Copy + PartialEq,
+{
+    /// The top left corner of the bounding box ends up at (0, 0)
+    fn from(sparse: &SparseGrid<T>) -> Self {
+        let (min, max) = match sparse.bounds() {
+            Some(bounds) => bounds,
+            None => return Grid::new(0, 0, sparse.default),
+        };
+
+        let n = (max.0 - min.0 + 1) as usize;
+        let m = (max.1 - min.1 + 1) as usize;
+        let mut grid = Grid::new(n, m, sparse.default);
+
+        for (coord, value) in sparse.iter() {
+            grid[coord - min] = *value;
+        }
+
+        grid
+    }
+}
+
+/// Draws the bounding box of the grid, see [Grid]'s [fmt::Display]
+impl<T> fmt::Display for SparseGrid<T>
+where
+    T: Glyph + Copy + PartialEq,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Grid::from(self).fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils;
+
+    #[test]
+    fn iterator_test() {
+        let lines = utils::split_lines("123\n456");
+        let grid: Grid<Digit> = lines.as_slice().try_into().expect("could not parse grid");
+        let grid = grid.map(|d| d.get());
+
+        assert_eq!(
+            grid.rows().collect::<Vec<_>>(),
+            vec![&[1, 2, 3], &[4, 5, 6]]
+        );
+        assert_eq!(grid.col(1).rev().copied().collect::<Vec<_>>(), vec![5, 2]);
+        assert_eq!(grid.cols().len(), 3);
+        assert_eq!(grid.cells().nth(4), Some((Coordinate(1, 1), &5)));
+        assert_eq!(
+            grid.find_all(|h| h % 2 == 0).collect::<Vec<_>>(),
+            vec![Coordinate(0, 1), Coordinate(1, 0), Coordinate(1, 2)]
+        );
+        assert_eq!(grid.get(Coordinate(2, 0)), None);
+        assert_eq!(grid.get(Coordinate(0, -1)), None);
+        assert_eq!(grid.neighbours(Coordinate(0, 0)).count(), 3);
+        assert_eq!(grid.cardinal_neighbours(Coordinate(1, 1)).count(), 3);
+        assert_eq!(grid.map(|h| Digit(9 - h)).to_string(), "876\n543");
+    }
+
+    #[test]
+    fn transform_test() {
+        let lines = utils::split_lines("123\n456");
+        let grid: Grid<Digit> = lines.as_slice().try_into().expect("could not parse grid");
+
+        assert_eq!(grid.transpose().to_string(), "14\n25\n36");
+        assert_eq!(grid.rotate_clockwise().to_string(), "41\n52\n63");
+        assert_eq!(grid.rotate_counterclockwise().to_string(), "36\n25\n14");
+        assert_eq!(grid.flip_horizontal().to_string(), "321\n654");
+        assert_eq!(grid.flip_vertical().to_string(), "456\n123");
+        assert_eq!(
+            grid.rotate_clockwise().rotate_clockwise().to_string(),
+            grid.flip_horizontal().flip_vertical().to_string()
+        );
+
+        let view = grid.view(Coordinate(0, 1), 2, 2).expect("view should fit");
+        assert_eq!(view[Coordinate(1, 0)], Digit(5));
+        assert_eq!(view.get(Coordinate(0, 2)), None);
+        assert_eq!(view.to_grid_coordinate(Coordinate(1, 1)), Coordinate(1, 2));
+        assert_eq!(view.cells().map(|(_, h)| h.get()).sum::<u8>(), 16);
+        assert_eq!(view.to_grid().to_string(), "23\n56");
+        assert!(grid.view(Coordinate(1, 1), 2, 2).is_none());
+    }
+
+    #[test]
+    fn coordinate_test() {
+        let c = Coordinate(2, -3);
+
+        assert_eq!(c + Coordinate(1, 1), Coordinate(3, -2));
+        assert_eq!(c - Coordinate(1, 1), Coordinate(1, -4));
+        assert_eq!(-c * 2, Coordinate(-4, 6));
+        assert_eq!(c.chebyshev_distance(&Coordinate(0, 0)), 3);
+        assert_eq!(Coordinate(0, 0).euclidean_distance(&Coordinate(3, 4)), 5.0);
+
+        let mut d = c;
+        d += Coordinate(0, 3);
+        d -= Coordinate(2, 0);
+        assert_eq!(d, Coordinate(0, 0));
+
+        // east turns into south when turning clockwise
+        let east = Coordinate(0, 0).east();
+        assert_eq!(east.rotate_clockwise(), Coordinate(0, 0).south());
+        assert_eq!(east.rotate_counterclockwise(), Coordinate(0, 0).north());
+        assert_eq!(c.rotate_clockwise().rotate_counterclockwise(), c);
+
+        // the conversions agree with x and y
+        let v = Vector2::from(c);
+        assert_eq!((v.x, v.y), (c.x(), c.y()));
+        assert_eq!(Coordinate::from(v.rotate_right()), c.rotate_clockwise());
+        assert_eq!(Point3::from(c), Point3::new(-3, -2, 0));
+        assert_eq!(Point3::from(c).to_coordinate(), c);
+    }
+
+    #[test]
+    fn direction_test() {
+        use Direction4::*;
+
+        assert_eq!(North.turn(Turn::Clockwise), East);
+        assert_eq!(North.turn(Turn::Counterclockwise), West);
+        assert_eq!(West.turn(Turn::Clockwise), North);
+        assert_eq!(South.reverse(), North);
+        assert_eq!(Turn::Clockwise.reverse(), Turn::Counterclockwise);
+
+        for dir in Direction4::ALL {
+            assert_eq!(dir.turn(Turn::Clockwise).turn(Turn::Counterclockwise), dir);
+            assert_eq!(
+                dir.offset(),
+                dir.offset().rotate_clockwise().rotate_counterclockwise()
+            );
+            assert_eq!(
+                dir.turn(Turn::Clockwise).offset(),
+                dir.offset().rotate_clockwise()
+            );
+            assert_eq!(Direction4::from_offset(dir.offset()), Some(dir));
+            assert_eq!(Direction4::from_glyph(dir.glyph()), Ok(dir));
+        }
+
+        for (s, dir) in [("U", North), ("E", East), ("v", South), ("L", West)] {
+            assert_eq!(s.parse::<Direction4>(), Ok(dir));
+        }
+        assert!("UU".parse::<Direction4>().is_err());
+        assert!("x".parse::<Direction4>().is_err());
+
+        assert_eq!(Direction8::from(East), Direction8::East);
+        assert_eq!(
+            Direction8::North.turn(Turn::Counterclockwise),
+            Direction8::NorthWest
+        );
+        assert_eq!(Direction8::SouthEast.reverse(), Direction8::NorthWest);
+        assert_eq!("SW".parse::<Direction8>(), Ok(Direction8::SouthWest));
+        assert_eq!("^".parse::<Direction8>(), Ok(Direction8::North));
+
+        let c = Coordinate(2, 2);
+        assert_eq!(c.step(East, 3), Coordinate(2, 5));
+        assert_eq!(c.step(Direction8::NorthWest, 2), Coordinate(0, 0));
+        assert_eq!(c.step(North, 1), c.north());
+        assert_eq!(c.step(South, 0), c);
+    }
+
+    #[test]
+    fn grid3_test() {
+        let mut grid = Grid3::new(2, 3, 4, 0);
+        grid[Point3::new(1, 2, 3)] = 7;
+        grid[Point3::new(0, 1, 0)] = 1;
+
+        assert_eq!(grid.get(Point3::new(1, 2, 3)), Some(&7));
+        assert_eq!(grid.get(Point3::new(2, 0, 0)), None);
+        assert_eq!(grid.get(Point3::new(0, -1, 0)), None);
+        assert_eq!(
+            grid.find_all(|&v| v > 0).collect::<Vec<_>>(),
+            vec![Point3::new(0, 1, 0), Point3::new(1, 2, 3)]
+        );
+        assert_eq!(grid.cells().count(), 24);
+        assert_eq!(grid.cardinal_neighbours(Point3::new(0, 0, 0)).count(), 3);
+        assert_eq!(grid.cardinal_neighbours(Point3::new(1, 1, 1)).count(), 5);
+        assert!(grid.is_on_edge(Point3::new(1, 1, 3)));
+        assert!(!grid.is_on_edge(Point3::new(1, 1, 4)));
+        assert_eq!(grid.map(|&v| v * 2)[Point3::new(1, 2, 3)], 14);
+
+        // a 3x3x3 grid has a single cell that is not on a face
+        let grid = Grid3::new(3, 3, 3, ());
+        let inside: Vec<_> = grid.cells().filter(|(p, _)| !grid.is_on_edge(*p)).collect();
+        assert_eq!(inside, vec![(Point3::new(1, 1, 1), &())]);
+    }
+
+    #[test]
+    fn render_test() {
+        let lines = utils::split_lines("#..\n.#.\n..#");
+        let grid: Grid<bool> = lines.as_slice().try_into().expect("could not parse grid");
+        assert_eq!(grid.to_string(), "#..\n.#.\n..#");
+        assert_eq!(
+            grid.render_with(
+                Glyph::glyph,
+                [(Coordinate(1, 1), '@'), (Coordinate(5, 5), '@')]
+            ),
+            "#..\n.@.\n..#"
+        );
+        assert_eq!(grid.render(|b| if *b { 'x' } else { ' ' }), "x  \n x \n  x");
+
+        let lines = utils::split_lines("30373\n25512");
+        let grid: Grid<Digit> = lines.as_slice().try_into().expect("could not parse grid");
+        assert_eq!(grid[Coordinate(0, 3)], Digit(7));
+        assert_eq!(utils::split_lines(&grid.to_string()), lines);
+
+        // values above 9 don't fit in one character, so they can't be digits
+        assert_eq!(Digit::new(9).map(|d| d.glyph()), Some('9'));
+        assert_eq!(Digit::new(10), None);
+
+        let lines = utils::split_lines("#.\n.x");
+        let actual = Grid::<bool>::try_from(lines.as_slice()).unwrap_err();
+        assert_eq!(actual.to_string(), "2:2: expected `#` or `.`");
+    }
+
+    #[test]
+    fn sparse_grid_test() {
+        let mut sparse = SparseGrid::new('.');
+        sparse.insert(Coordinate(-2, 3), '#');
+        sparse.insert(Coordinate(1, -1), '#');
+        sparse[Coordinate(0, 0)] = 'x';
+
+        assert_eq!(sparse.len(), 3);
+        assert_eq!(sparse[Coordinate(-2, 3)], '#');
+        assert_eq!(sparse[Coordinate(5, 5)], '.');
+        assert_eq!(
+            sparse.bounds(),
+            Some((Coordinate(-2, -1), Coordinate(1, 3)))
+        );
+        assert!(sparse.is_in_bounds(Coordinate(-1, 2)));
+        assert!(!sparse.is_in_bounds(Coordinate(2, 2)));
+        assert_eq!(sparse.neighbours(Coordinate(1, 0)).count(), 2);
+        assert_eq!(sparse.cardinal_neighbours(Coordinate(0, 1)).count(), 1);
+
+        // removing a cell on the edge shrinks the bounding box
+        assert_eq!(sparse.remove(Coordinate(-2, 3)), Some('#'));
+        assert_eq!(sparse.bounds.get(), None);
+        assert_eq!(sparse.bounds(), Some((Coordinate(0, -1), Coordinate(1, 0))));
+        assert_eq!(sparse.remove(Coordinate(-2, 3)), None);
+
+        // the shrunk box is kept, so it is only recomputed once
+        assert!(sparse.bounds.get().is_some());
+        assert!(!sparse.is_in_bounds(Coordinate(-2, 3)));
+
+        // removing every cell leaves no bounding box, until one is set again
+        sparse.remove(Coordinate(0, 0));
+        sparse.remove(Coordinate(1, -1));
+        assert_eq!(sparse.bounds(), None);
+        sparse.insert(Coordinate(4, 4), '#');
+        assert_eq!(sparse.bounds(), Some((Coordinate(4, 4), Coordinate(4, 4))));
+    }
+
+    #[test]
+    fn sparse_grid_conversion_test() {
+        let mut sparse = SparseGrid::default();
+        sparse.insert(Coordinate(-1, -1), true);
+        sparse.insert(Coordinate(0, 1), true);
+
+        let grid = Grid::from(&sparse);
+        assert_eq!(grid.to_string(), "#..\n..#");
+
+        let sparse = SparseGrid::from(grid);
+        assert_eq!(sparse.len(), 2);
+        assert!(sparse.contains(Coordinate(0, 0)));
+        assert!(sparse.contains(Coordinate(1, 2)));
+        assert_eq!(sparse.bounds(), Some((Coordinate(0, 0), Coordinate(1, 2))));
     }
 }
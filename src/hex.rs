@@ -0,0 +1,106 @@
+use std::fmt;
+use std::ops::{Add, Sub};
+
+/// A hexagon on a grid using axial coordinates, the third cube coordinate `s` is implied by
+/// `q + r + s = 0`
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+pub struct Hex {
+    pub q: isize,
+    pub r: isize,
+}
+
+impl Hex {
+    /// The offsets to the 6 neighbours, starting east and going counterclockwise
+    pub const DIRECTIONS: [Hex; 6] = [
+        Hex::new(1, 0),
+        Hex::new(1, -1),
+        Hex::new(0, -1),
+        Hex::new(-1, 0),
+        Hex::new(-1, 1),
+        Hex::new(0, 1),
+    ];
+
+    pub const fn new(q: isize, r: isize) -> Self {
+        Self { q, r }
+    }
+
+    /// Builds a hex from cube coordinates, panics if they don't add up to 0
+    pub fn from_cube(q: isize, r: isize, s: isize) -> Self {
+        assert_eq!(q + r + s, 0, "cube coordinates must add up to 0");
+        Self { q, r }
+    }
+
+    pub fn s(&self) -> isize {
+        -self.q - self.r
+    }
+
+    pub fn to_cube(self) -> (isize, isize, isize) {
+        (self.q, self.r, self.s())
+    }
+
+    pub fn neighbours(&self) -> [Self; 6] {
+        Self::DIRECTIONS.map(|d| *self + d)
+    }
+
+    /// The number of steps between two hexes
+    pub fn distance(&self, other: &Self) -> usize {
+        let d = *self - *other;
+        d.q.unsigned_abs()
+            .max(d.r.unsigned_abs())
+            .max(d.s().unsigned_abs())
+    }
+}
+
+impl fmt::Display for Hex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "H({}, {})", self.q, self.r)
+    }
+}
+
+impl From<(isize, isize)> for Hex {
+    fn from(v: (isize, isize)) -> Self {
+        Self::new(v.0, v.1)
+    }
+}
+
+impl Add for Hex {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.q + rhs.q, self.r + rhs.r)
+    }
+}
+
+impl Sub for Hex {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.q - rhs.q, self.r - rhs.r)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hex_test() {
+        let origin = Hex::default();
+
+        for n in origin.neighbours() {
+            assert_eq!(origin.distance(&n), 1);
+            assert_eq!(n.to_cube().0 + n.to_cube().1 + n.to_cube().2, 0);
+        }
+
+        let h = Hex::from_cube(3, -1, -2);
+        assert_eq!(h, Hex::new(3, -1));
+        assert_eq!(h.s(), -2);
+        assert_eq!(h.distance(&origin), 3);
+        assert_eq!(origin.distance(&h), 3);
+        assert_eq!(Hex::new(-2, 4).distance(&Hex::new(1, -1)), 5);
+
+        // walking around a neighbour's ring comes back to the start
+        let ring = Hex::DIRECTIONS.iter().fold(Hex::new(1, 0), |h, &d| h + d);
+        assert_eq!(ring, Hex::new(1, 0));
+    }
+}
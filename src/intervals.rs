@@ -0,0 +1,322 @@
+use std::fmt;
+use std::ops::{Add, RangeInclusive, Sub};
+
+/// An integer type that can be used as the bounds of an [Interval]
+pub trait Bound: Copy + Ord + Add<Output = Self> + Sub<Output = Self> {
+    const ZERO: Self;
+    const ONE: Self;
+}
+
+macro_rules! impl_bound {
+    ($($t:ty),+) => {
+        $(impl Bound for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+        })+
+    };
+}
+
+impl_bound!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// An inclusive range of integers, like [RangeInclusive] but [Copy] and with set operations
+///
+/// The interval is empty when [start] is after [end].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: Bound> Interval<T> {
+    pub fn new(start: T, end: T) -> Self {
+        Self { start, end }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start > self.end
+    }
+
+    /// The number of integers in the interval
+    pub fn len(&self) -> T {
+        if self.is_empty() {
+            T::ZERO
+        } else {
+            self.end - self.start + T::ONE
+        }
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.start <= value && value <= self.end
+    }
+
+    /// Returns `true` if every integer of [other] is also in this interval
+    pub fn contains_interval(&self, other: &Self) -> bool {
+        other.is_empty() || (self.start <= other.start && other.end <= self.end)
+    }
+
+    /// Returns `true` if the 2 intervals have at least 1 integer in common
+    pub fn overlaps(&self, other: &Self) -> bool {
+        !self.intersection(other).is_empty()
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        Self::new(self.start.max(other.start), self.end.min(other.end))
+    }
+}
+
+impl<T> From<RangeInclusive<T>> for Interval<T> {
+    fn from(range: RangeInclusive<T>) -> Self {
+        let (start, end) = range.into_inner();
+        Self { start, end }
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Interval<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}, {}]", self.start, self.end)
+    }
+}
+
+/// A set of integers stored as sorted, disjoint intervals
+///
+/// Intervals that overlap or touch are merged, so there is always a gap between 2 consecutive
+/// intervals.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T: Bound> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self {
+            intervals: Vec::new(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// The total number of integers in the set
+    pub fn len(&self) -> T {
+        self.intervals
+            .iter()
+            .fold(T::ZERO, |total, interval| total + interval.len())
+    }
+
+    /// Iterates over the disjoint intervals in increasing order
+    pub fn iter(&self) -> impl Iterator<Item = Interval<T>> + '_ {
+        self.intervals.iter().copied()
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        // the first interval that doesn't end before the value is the only one that can hold it
+        let i = self
+            .intervals
+            .partition_point(|interval| interval.end < value);
+        self.intervals.get(i).is_some_and(|i| i.contains(value))
+    }
+
+    /// Adds every integer of [interval] to the set
+    pub fn insert(&mut self, interval: Interval<T>) {
+        if interval.is_empty() {
+            return;
+        }
+
+        // the intervals to merge with are the ones that overlap or touch the new one
+        let first = self
+            .intervals
+            .partition_point(|i| i.end < interval.start && i.end + T::ONE < interval.start);
+        let last = self
+            .intervals
+            .partition_point(|i| i.start <= interval.end || i.start - T::ONE <= interval.end);
+
+        let merged = self.intervals[first..last]
+            .iter()
+            .fold(interval, |merged, i| {
+                Interval::new(merged.start.min(i.start), merged.end.max(i.end))
+            });
+
+        self.intervals.splice(first..last, [merged]);
+    }
+
+    /// Removes a single integer from the set, splitting the interval that contains it
+    pub fn remove(&mut self, value: T) -> bool {
+        let i = self
+            .intervals
+            .partition_point(|interval| interval.end < value);
+
+        match self.intervals.get(i) {
+            Some(&interval) if interval.contains(value) => {
+                let parts = [
+                    (value > interval.start).then(|| Interval::new(interval.start, value - T::ONE)),
+                    (value < interval.end).then(|| Interval::new(value + T::ONE, interval.end)),
+                ];
+
+                self.intervals.splice(i..=i, parts.into_iter().flatten());
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        for &interval in &other.intervals {
+            union.insert(interval);
+        }
+
+        union
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut intervals = Vec::new();
+        let (mut i, mut j) = (0, 0);
+
+        while i < self.intervals.len() && j < other.intervals.len() {
+            let (a, b) = (self.intervals[i], other.intervals[j]);
+            let common = a.intersection(&b);
+
+            if !common.is_empty() {
+                intervals.push(common);
+            }
+
+            // whichever interval ends first can't overlap anything else
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        Self { intervals }
+    }
+
+    /// Returns the integers of this set that are not in [other]
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut intervals = Vec::new();
+        let mut j = 0;
+
+        for &interval in &self.intervals {
+            // skip the intervals of the other set that end before this one starts
+            while j < other.intervals.len() && other.intervals[j].end < interval.start {
+                j += 1;
+            }
+
+            // cut away every interval that overlaps, keeping whatever comes before it
+            let mut rest = Some(interval);
+            for cut in other.intervals[j..]
+                .iter()
+                .take_while(|c| c.start <= interval.end)
+            {
+                let Some(r) = rest else { break };
+
+                if cut.start > r.start {
+                    intervals.push(Interval::new(r.start, cut.start - T::ONE));
+                }
+
+                rest = (cut.end < r.end).then(|| Interval::new(cut.end + T::ONE, r.end));
+            }
+
+            intervals.extend(rest);
+        }
+
+        Self { intervals }
+    }
+
+    /// Iterates over the integers missing between the first and the last interval
+    pub fn gaps(&self) -> impl Iterator<Item = Interval<T>> + '_ {
+        self.intervals
+            .windows(2)
+            .map(|w| Interval::new(w[0].end + T::ONE, w[1].start - T::ONE))
+    }
+}
+
+impl<T: Bound> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut sorted: Vec<_> = iter.into_iter().filter(|i| !i.is_empty()).collect();
+        sorted.sort_unstable_by_key(|i| i.start);
+
+        let mut intervals: Vec<Interval<T>> = Vec::with_capacity(sorted.len());
+
+        for interval in sorted {
+            match intervals.last_mut() {
+                // the interval overlaps or touches the ongoing one, so we just extend it
+                Some(last) if interval.start <= last.end || interval.start - T::ONE <= last.end => {
+                    last.end = last.end.max(interval.end);
+                }
+                _ => intervals.push(interval),
+            }
+        }
+
+        Self { intervals }
+    }
+}
+
+impl<T: Bound> From<Interval<T>> for IntervalSet<T> {
+    fn from(interval: Interval<T>) -> Self {
+        Self::from_iter([interval])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(intervals: &[(i32, i32)]) -> IntervalSet<i32> {
+        intervals
+            .iter()
+            .map(|&(s, e)| Interval::new(s, e))
+            .collect()
+    }
+
+    #[test]
+    fn interval_test() {
+        let a = Interval::new(2, 6);
+        let b = Interval::from(4..=8);
+
+        assert_eq!(a.len(), 5);
+        assert!(a.contains(2) && a.contains(6) && !a.contains(7));
+        assert!(a.overlaps(&b));
+        assert!(!a.overlaps(&Interval::new(7, 9)));
+        assert!(a.contains_interval(&Interval::new(3, 6)));
+        assert!(!a.contains_interval(&b));
+        assert_eq!(a.intersection(&b), Interval::new(4, 6));
+
+        let empty = Interval::new(3_usize, 2);
+        assert!(empty.is_empty());
+        assert_eq!(empty.len(), 0);
+    }
+
+    #[test]
+    fn interval_set_test() {
+        // overlapping and touching intervals are merged
+        let a = set(&[(12, 12), (-2, 2), (2, 2), (0, 4), (5, 6), (10, 10)]);
+        assert_eq!(a, set(&[(-2, 6), (10, 10), (12, 12)]));
+        assert_eq!(a.len(), 11);
+        assert!(a.contains(6) && a.contains(12) && !a.contains(11));
+        assert_eq!(
+            a.gaps().collect::<Vec<_>>(),
+            vec![(7..=9).into(), (11..=11).into()]
+        );
+
+        let mut b = a.clone();
+        b.insert(Interval::new(7, 8));
+        assert_eq!(b, set(&[(-2, 8), (10, 10), (12, 12)]));
+        b.insert(Interval::new(9, 11));
+        assert_eq!(b, set(&[(-2, 12)]));
+
+        assert!(b.remove(0));
+        assert!(!b.remove(0));
+        assert!(b.remove(-2));
+        assert!(b.remove(12));
+        assert_eq!(b, set(&[(-1, -1), (1, 11)]));
+
+        let c = set(&[(0, 3), (5, 10)]);
+        assert_eq!(a.union(&c), set(&[(-2, 10), (12, 12)]));
+        assert_eq!(a.intersection(&c), set(&[(0, 3), (5, 6), (10, 10)]));
+        assert_eq!(a.difference(&c), set(&[(-2, -1), (4, 4), (12, 12)]));
+        assert_eq!(c.difference(&a), set(&[(7, 9)]));
+        assert!(a.difference(&a).is_empty());
+    }
+}
@@ -1,6 +1,13 @@
 pub mod algebra;
+pub mod answers;
 pub mod grid;
+pub mod hex;
+pub mod intervals;
 pub mod linked_list;
+pub mod parser;
+pub mod runner;
+pub mod scaffold;
+pub mod search;
 pub mod solution;
 pub mod utils;
 
@@ -19,11 +26,13 @@ pub mod day_12;
 pub mod day_13;
 pub mod day_14;
 pub mod day_15;
+pub mod day_16;
 pub mod day_17;
 pub mod day_18;
+pub mod day_19;
 pub mod day_20;
 pub mod day_21;
 pub mod day_22;
 pub mod day_23;
+pub mod day_24;
 pub mod day_25;
-// ${LIB_IMPORT_MARKER}
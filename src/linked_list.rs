@@ -60,7 +60,7 @@ impl From<ListNode> for LinkWrapper {
 
 impl From<Option<ListNode>> for LinkWrapper {
     fn from(node: Option<ListNode>) -> Self {
-        Self(node.and_then(|x| Some(Rc::new(x))))
+        Self(node.map(Rc::new))
     }
 }
 
@@ -0,0 +1,413 @@
+//! A small set of parser combinators for puzzle inputs
+//!
+//! A parser takes the rest of a line and returns the parsed value together with whatever it did
+//! not consume, so parsers can be chained:
+//!
+//!   let coordinate = pair(terminated(integer(), literal(",")), integer());
+//!   let path = separated_list(coordinate, literal(" -> "));
+//!   let rocks: Vec<(u32, u32)> = parse_line("498,4 -> 498,6", path)?;
+//!
+//! Failures remember where in the line they happened, so [parse_line] can turn them into a
+//! [ParseError] that points at the offending column.
+
+use std::str::FromStr;
+
+use crate::utils::ParseError;
+
+/// A parser that could not match, and the rest of the line at the point where it gave up
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Failure<'a> {
+    pub expected: String,
+    pub rest: &'a str,
+}
+
+impl<'a> Failure<'a> {
+    pub fn new(expected: impl Into<String>, rest: &'a str) -> Self {
+        Self {
+            expected: expected.into(),
+            rest,
+        }
+    }
+
+    /// Converts the failure into a [ParseError] pointing into [line]
+    pub fn into_error(self, line: &str) -> ParseError {
+        ParseError::new(self.expected).at_offset(line.len() - self.rest.len())
+    }
+}
+
+pub type PResult<'a, T> = Result<(T, &'a str), Failure<'a>>;
+
+pub trait Parser<'a, T> {
+    fn parse(&self, input: &'a str) -> PResult<'a, T>;
+
+    /// Parses the whole [line], failing if anything is left over
+    fn parse_line(&self, line: &'a str) -> Result<T, ParseError> {
+        let (value, rest) = self.parse(line).map_err(|f| f.into_error(line))?;
+
+        if !rest.is_empty() {
+            return Err(Failure::new("the end of the line", rest).into_error(line));
+        }
+
+        Ok(value)
+    }
+}
+
+impl<'a, T, F> Parser<'a, T> for F
+where
+    F: Fn(&'a str) -> PResult<'a, T>,
+{
+    fn parse(&self, input: &'a str) -> PResult<'a, T> {
+        self(input)
+    }
+}
+
+/// Runs [parser] over the whole [line], failing if anything is left over
+pub fn parse_line<'a, T>(line: &'a str, parser: impl Parser<'a, T>) -> Result<T, ParseError> {
+    parser.parse_line(line)
+}
+
+/// Runs [parser] over each of the [lines], numbering the lines in any errors
+pub fn each_line<'a, T>(
+    lines: &'a [String],
+    parser: impl Parser<'a, T>,
+) -> Result<Vec<T>, ParseError> {
+    lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| parser.parse_line(line).map_err(|e| e.at_line(i)))
+        .collect()
+}
+
+/// Splits [lines] into blocks separated by an empty line. Each block comes with the 0-based
+/// index of its first line.
+pub fn blocks(lines: &[String]) -> impl Iterator<Item = (usize, &[String])> {
+    let mut offset = 0;
+
+    lines.split(|line| line.is_empty()).map(move |block| {
+        let start = offset;
+        offset += block.len() + 1;
+        (start, block)
+    })
+}
+
+/// Matches exactly [tag]
+pub fn literal<'a>(tag: &'static str) -> impl Parser<'a, &'a str> {
+    move |input: &'a str| match input.strip_prefix(tag) {
+        Some(rest) => Ok((&input[..tag.len()], rest)),
+        None => {
+            // point at the first character that differs
+            let matched = input
+                .char_indices()
+                .zip(tag.chars())
+                .find(|((_, a), b)| a != b)
+                .map_or(input.len().min(tag.len()), |((i, _), _)| i);
+
+            Err(Failure::new(format!("`{}`", tag), &input[matched..]))
+        }
+    }
+}
+
+/// Matches an integer with an optional sign, like `42`, `-7` or `+3`
+pub fn integer<'a, T: FromStr>() -> impl Parser<'a, T> {
+    move |input: &'a str| {
+        let sign = usize::from(input.starts_with(['-', '+']));
+        let len = sign
+            + input[sign..]
+                .bytes()
+                .take_while(|b| b.is_ascii_digit())
+                .count();
+
+        match input[..len].parse() {
+            Ok(value) if len > sign => Ok((value, &input[len..])),
+            _ => Err(Failure::new("a number", input)),
+        }
+    }
+}
+
+/// Matches a single character
+pub fn any_char<'a>() -> impl Parser<'a, char> {
+    move |input: &'a str| {
+        let mut chars = input.chars();
+
+        match chars.next() {
+            Some(c) => Ok((c, chars.as_str())),
+            None => Err(Failure::new("a character", input)),
+        }
+    }
+}
+
+/// Matches one or more characters satisfying [predicate]
+pub fn take_while1<'a>(
+    expected: &'static str,
+    predicate: impl Fn(char) -> bool,
+) -> impl Parser<'a, &'a str> {
+    move |input: &'a str| {
+        let len = input.find(|c| !predicate(c)).unwrap_or(input.len());
+
+        if len == 0 {
+            return Err(Failure::new(expected, input));
+        }
+
+        Ok((&input[..len], &input[len..]))
+    }
+}
+
+/// Matches a run of non-whitespace characters
+pub fn word<'a>() -> impl Parser<'a, &'a str> {
+    take_while1("a word", |c| !c.is_whitespace())
+}
+
+/// Matches everything up to the end of the line, which may be nothing
+pub fn rest<'a>() -> impl Parser<'a, &'a str> {
+    move |input: &'a str| Ok((input, &input[input.len()..]))
+}
+
+pub fn map<'a, A, B>(parser: impl Parser<'a, A>, f: impl Fn(A) -> B) -> impl Parser<'a, B> {
+    move |input: &'a str| parser.parse(input).map(|(a, rest)| (f(a), rest))
+}
+
+/// Like [map], but [f] may reject the value, in which case the failure points at the start of
+/// the value. Errors from [FromStr] implementations keep their column relative to the value.
+pub fn try_map<'a, A, B>(
+    parser: impl Parser<'a, A>,
+    f: impl Fn(A) -> Result<B, ParseError>,
+) -> impl Parser<'a, B> {
+    move |input: &'a str| {
+        let (a, rest) = parser.parse(input)?;
+
+        match f(a) {
+            Ok(b) => Ok((b, rest)),
+            Err(e) => {
+                let offset = e.column.map_or(0, |c| c - 1).min(input.len());
+                Err(Failure::new(e.expected, &input[offset..]))
+            }
+        }
+    }
+}
+
+pub fn pair<'a, A, B>(
+    first: impl Parser<'a, A>,
+    second: impl Parser<'a, B>,
+) -> impl Parser<'a, (A, B)> {
+    move |input: &'a str| {
+        let (a, rest) = first.parse(input)?;
+        let (b, rest) = second.parse(rest)?;
+        Ok(((a, b), rest))
+    }
+}
+
+/// Matches [prefix] then [parser], keeping only the latter
+pub fn preceded<'a, A, B>(
+    prefix: impl Parser<'a, A>,
+    parser: impl Parser<'a, B>,
+) -> impl Parser<'a, B> {
+    map(pair(prefix, parser), |(_, b)| b)
+}
+
+/// Matches [parser] then [suffix], keeping only the former
+pub fn terminated<'a, A, B>(
+    parser: impl Parser<'a, A>,
+    suffix: impl Parser<'a, B>,
+) -> impl Parser<'a, A> {
+    map(pair(parser, suffix), |(a, _)| a)
+}
+
+pub fn delimited<'a, A, B, C>(
+    open: impl Parser<'a, A>,
+    parser: impl Parser<'a, B>,
+    close: impl Parser<'a, C>,
+) -> impl Parser<'a, B> {
+    preceded(open, terminated(parser, close))
+}
+
+/// Tries [first], then [second]. If both fail, the failure that got furthest is kept.
+pub fn or<'a, T>(first: impl Parser<'a, T>, second: impl Parser<'a, T>) -> impl Parser<'a, T> {
+    move |input: &'a str| {
+        let a = match first.parse(input) {
+            Ok(ret) => return Ok(ret),
+            Err(a) => a,
+        };
+
+        second
+            .parse(input)
+            .map_err(|b| match a.rest.len().cmp(&b.rest.len()) {
+                std::cmp::Ordering::Less => a,
+                std::cmp::Ordering::Greater => b,
+                std::cmp::Ordering::Equal => {
+                    Failure::new(format!("{} or {}", a.expected, b.expected), b.rest)
+                }
+            })
+    }
+}
+
+pub fn opt<'a, T>(parser: impl Parser<'a, T>) -> impl Parser<'a, Option<T>> {
+    move |input: &'a str| match parser.parse(input) {
+        Ok((value, rest)) => Ok((Some(value), rest)),
+        Err(_) => Ok((None, input)),
+    }
+}
+
+/// Matches [parser] as many times as possible, possibly zero
+pub fn many<'a, T>(parser: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    move |mut input: &'a str| {
+        let mut values = Vec::default();
+
+        while let Ok((value, rest)) = parser.parse(input) {
+            if rest.len() == input.len() {
+                break;
+            }
+
+            values.push(value);
+            input = rest;
+        }
+
+        Ok((values, input))
+    }
+}
+
+/// Matches one or more [item]s with a [separator] between each of them
+pub fn separated_list<'a, T, S>(
+    item: impl Parser<'a, T>,
+    separator: impl Parser<'a, S>,
+) -> impl Parser<'a, Vec<T>> {
+    move |input: &'a str| {
+        let (first, mut input) = item.parse(input)?;
+        let mut values = vec![first];
+
+        while let Ok((_, rest)) = separator.parse(input) {
+            let (value, rest) = item.parse(rest)?;
+            values.push(value);
+            input = rest;
+        }
+
+        Ok((values, input))
+    }
+}
+
+/// Matches a `key: value` record
+pub fn key_value<'a, K, V>(
+    key: impl Parser<'a, K>,
+    value: impl Parser<'a, V>,
+) -> impl Parser<'a, (K, V)> {
+    pair(terminated(key, literal(": ")), value)
+}
+
+/// An item in a [nested_list]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Nested<T> {
+    Item(T),
+    List(Vec<Nested<T>>),
+}
+
+/// Matches a bracketed, comma separated list whose elements are either [item]s or nested lists
+/// themselves, like `[1,[2,[]],3]`
+pub fn nested_list<'a, T>(item: impl Parser<'a, T>) -> impl Parser<'a, Vec<Nested<T>>> {
+    move |input: &'a str| bracketed(&item, input)
+}
+
+fn bracketed<'a, T>(item: &impl Parser<'a, T>, input: &'a str) -> PResult<'a, Vec<Nested<T>>> {
+    let (_, mut input) = literal("[").parse(input)?;
+    let mut list = Vec::default();
+
+    if let Some(rest) = input.strip_prefix(']') {
+        return Ok((list, rest));
+    }
+
+    loop {
+        let (value, rest) = if input.starts_with('[') {
+            let (list, rest) = bracketed(item, input)?;
+            (Nested::List(list), rest)
+        } else {
+            let (value, rest) = item
+                .parse(input)
+                .map_err(|f| Failure::new(format!("`[` or {}", f.expected), f.rest))?;
+            (Nested::Item(value), rest)
+        };
+
+        list.push(value);
+
+        match rest.strip_prefix(',') {
+            Some(rest) => input = rest,
+            None => match rest.strip_prefix(']') {
+                Some(rest) => return Ok((list, rest)),
+                None => return Err(Failure::new("`,` or `]`", rest)),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integer_test() {
+        assert_eq!(parse_line("42", integer::<u32>()), Ok(42));
+        assert_eq!(parse_line("-42", integer::<i32>()), Ok(-42));
+        assert_eq!(parse_line("+42", integer::<i32>()), Ok(42));
+        assert_eq!(
+            parse_line("-42", integer::<u32>()).map_err(|e| e.to_string()),
+            Err("1: expected a number".to_owned())
+        );
+        assert_eq!(
+            parse_line("42x", integer::<u32>()).map_err(|e| e.to_string()),
+            Err("3: expected the end of the line".to_owned())
+        );
+    }
+
+    #[test]
+    fn combinator_test() {
+        let path = || {
+            let coordinate = pair(terminated(integer::<u32>(), literal(",")), integer::<u32>());
+            separated_list(coordinate, literal(" -> "))
+        };
+        assert_eq!(
+            parse_line("498,4 -> 498,6 -> 496,6", path()),
+            Ok(vec![(498, 4), (498, 6), (496, 6)])
+        );
+        assert_eq!(
+            parse_line("498,4 -> 498;6", path()).map_err(|e| e.to_string()),
+            Err("13: expected `,`".to_owned())
+        );
+
+        let key = take_while1("a key", |c| c != ':');
+        let record = key_value(key, separated_list(integer::<u64>(), literal(", ")));
+        assert_eq!(
+            parse_line("items: 79, 98", record),
+            Ok(("items", vec![79, 98]))
+        );
+
+        let instruction = || {
+            or(
+                map(literal("noop"), |_| None),
+                map(preceded(literal("addx "), integer::<i32>()), Some),
+            )
+        };
+        assert_eq!(parse_line("addx -3", instruction()), Ok(Some(-3)));
+        assert_eq!(
+            parse_line("addy 3", instruction()).map_err(|e| e.to_string()),
+            Err("4: expected `addx `".to_owned())
+        );
+    }
+
+    #[test]
+    fn nested_list_test() {
+        use Nested::*;
+
+        assert_eq!(
+            parse_line("[1,[2,[]],3]", nested_list(integer::<u8>())),
+            Ok(vec![Item(1), List(vec![Item(2), List(vec![])]), Item(3)])
+        );
+        assert_eq!(
+            parse_line("[1,[2,3]", nested_list(integer::<u8>())).map_err(|e| e.to_string()),
+            Err("9: expected `,` or `]`".to_owned())
+        );
+    }
+
+    #[test]
+    fn blocks_test() {
+        let lines = crate::utils::split_lines("a\nb\n\nc");
+        let actual: Vec<_> = blocks(&lines).map(|(i, block)| (i, block.len())).collect();
+        assert_eq!(actual, vec![(0, 2), (3, 1)]);
+    }
+}
@@ -0,0 +1,624 @@
+use std::{
+    any::Any,
+    env, fmt, fs, hint,
+    num::NonZeroUsize,
+    panic,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::{bail, Context, Error, Result};
+use serde::Serialize;
+
+use crate::{
+    day_01, day_02, day_03, day_04, day_05, day_06, day_07, day_08, day_09, day_10, day_11, day_12,
+    day_13, day_14, day_15, day_16, day_17, day_18, day_19, day_20, day_21, day_22, day_23, day_24,
+    day_25,
+};
+use crate::{
+    solution::Day,
+    utils::{Input, ParseError},
+};
+
+/// A type-erased answer to one part of a puzzle
+///
+/// Keeps both the display form (what the examples print) and the json form (what
+/// `AOC_OUTPUT_JSON` prints), so answers of different types can be reported the same way.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Answer {
+    text: String,
+    value: serde_json::Value,
+}
+
+impl Answer {
+    pub fn new<T>(answer: &T) -> Self
+    where
+        T: fmt::Display + Serialize,
+    {
+        Self {
+            text: answer.to_string(),
+            value: serde_json::to_value(answer).expect("Unable to convert answer to json"),
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+impl Serialize for Answer {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.value.serialize(serializer)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl FromStr for Part {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" | "one" => Ok(Self::One),
+            "2" | "two" => Ok(Self::Two),
+            _ => bail!("Invalid part: {}", s),
+        }
+    }
+}
+
+/// How long each phase of a run took
+///
+/// There is no separate loading phase: days parse the input while it is being read, so reading it
+/// is timed as part of parsing.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Serialize)]
+pub struct Timings {
+    #[serde(rename = "parse_ns", serialize_with = "serialize_nanos")]
+    pub parse: Duration,
+    #[serde(rename = "part_one_ns", serialize_with = "serialize_optional_nanos")]
+    pub part_one: Option<Duration>,
+    #[serde(rename = "part_two_ns", serialize_with = "serialize_optional_nanos")]
+    pub part_two: Option<Duration>,
+}
+
+impl Timings {
+    pub fn total(&self) -> Duration {
+        self.parse + self.part_one.unwrap_or_default() + self.part_two.unwrap_or_default()
+    }
+}
+
+impl fmt::Display for Timings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "parse {:.2?}", self.parse)?;
+
+        if let Some(duration) = self.part_one {
+            write!(f, ", part 1 {:.2?}", duration)?;
+        }
+
+        if let Some(duration) = self.part_two {
+            write!(f, ", part 2 {:.2?}", duration)?;
+        }
+
+        write!(f, ", total {:.2?}", self.total())
+    }
+}
+
+fn serialize_nanos<S>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serializer.serialize_u128(duration.as_nanos())
+}
+
+fn serialize_optional_nanos<S>(
+    duration: &Option<Duration>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    match duration {
+        Some(d) => serialize_nanos(d, serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// Runs [f] and returns its result together with how long it took
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let ret = f();
+    (ret, start.elapsed())
+}
+
+/// The answers and timings produced by running a single day against a single input
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Report {
+    pub day: u8,
+    pub title: &'static str,
+    pub input: String,
+    pub part_one: Option<Answer>,
+    pub part_two: Option<Answer>,
+    pub timings: Timings,
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if env::var("AOC_OUTPUT_JSON").is_ok() {
+            return write!(
+                f,
+                "{}",
+                serde_json::to_string(&self).expect("Unable to convert self to json")
+            );
+        }
+
+        write!(f, "day {:02}: {} ({})", self.day, self.title, self.input)?;
+
+        if let Some(answer) = &self.part_one {
+            write!(f, "\npart 1: {}", answer)?;
+        }
+
+        if let Some(answer) = &self.part_two {
+            write!(f, "\npart 2: {}", answer)?;
+        }
+
+        write!(f, "\ntimings: {}", self.timings)
+    }
+}
+
+type Solver = fn(Input, Option<Part>, &mut Timings) -> Result<(Option<Answer>, Option<Answer>)>;
+
+/// A day's parsed input with its type erased, so that each phase of any day can be run on its
+/// own, like the benches do
+pub struct ParsedInput(Box<dyn Any>);
+
+/// A day that the runner knows how to solve
+pub struct Entry {
+    pub day: u8,
+    pub title: &'static str,
+    solver: Solver,
+    parallel_solver: Option<Solver>,
+    parse: fn(Input) -> Result<ParsedInput>,
+    part_one: fn(&ParsedInput),
+    part_two: fn(&ParsedInput),
+}
+
+impl Entry {
+    pub const fn new<D: Day>() -> Self
+    where
+        D::Parsed: 'static,
+    {
+        Self {
+            day: D::DAY,
+            title: D::TITLE,
+            solver: solve::<D>,
+            parallel_solver: None,
+            parse: parse_erased::<D>,
+            part_one: part_one_erased::<D>,
+            part_two: part_two_erased::<D>,
+        }
+    }
+
+    /// Like [Entry::new], but the parsed input can be shared between threads, so both parts can be
+    /// solved at the same time
+    pub const fn shared<D: Day>() -> Self
+    where
+        D::Parsed: Sync + 'static,
+    {
+        Self {
+            parallel_solver: Some(solve_parallel::<D>),
+            ..Self::new::<D>()
+        }
+    }
+
+    /// Returns the path of the puzzle input that is used when no other input is given
+    pub fn default_input(&self) -> String {
+        format!("inputs/day_{:02}", self.day)
+    }
+
+    /// Returns every input of this day in [dir], like `day_08`, `day_08.example` and
+    /// `day_08.large`, sorted by name so the default input comes first
+    pub fn inputs(&self, dir: &Path) -> Result<Vec<PathBuf>> {
+        let name = format!("day_{:02}", self.day);
+        let mut inputs = Vec::new();
+
+        for file in
+            fs::read_dir(dir).with_context(|| format!("could not list {}", dir.display()))?
+        {
+            let path = file?.path();
+            let matches = path
+                .file_name()
+                .and_then(|f| f.to_str())
+                .and_then(|f| f.strip_prefix(&name))
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'));
+
+            if matches {
+                inputs.push(path);
+            }
+        }
+
+        inputs.sort_unstable();
+        Ok(inputs)
+    }
+
+    /// Parses the [input] the same way [Entry::run] does, without solving anything
+    pub fn parse(&self, input: Input) -> Result<ParsedInput> {
+        (self.parse)(input)
+    }
+
+    /// Solves part one of the [parsed] input and throws the answer away
+    ///
+    /// Panics if the input was parsed by another day.
+    pub fn part_one(&self, parsed: &ParsedInput) {
+        (self.part_one)(parsed)
+    }
+
+    /// Solves part two of the [parsed] input and throws the answer away
+    ///
+    /// Panics if the input was parsed by another day.
+    pub fn part_two(&self, parsed: &ParsedInput) {
+        (self.part_two)(parsed)
+    }
+
+    /// Opens the [input], parses it as it is read and solves the requested [part], or both parts
+    /// if no part is given. Parsing, including reading the input, and each part are timed
+    /// separately.
+    ///
+    /// Both parts are solved in parallel if the entry is [Entry::shared].
+    pub fn run(&self, input: &str, part: Option<Part>) -> Result<Report> {
+        self.run_with(input, part, true)
+    }
+
+    fn run_with(&self, input: &str, part: Option<Part>, parallel: bool) -> Result<Report> {
+        let mut timings = Timings::default();
+
+        let opened = Input::open(input).with_context(|| format!("could not load {}", input))?;
+
+        let solver = match self.parallel_solver {
+            Some(solver) if parallel => solver,
+            _ => self.solver,
+        };
+
+        let (part_one, part_two) = solver(opened, part, &mut timings)
+            .map_err(|e| match e.downcast::<ParseError>() {
+                Ok(e) => e.with_path(input).into(),
+                Err(e) => e,
+            })
+            .with_context(|| format!("could not solve day {:02}", self.day))?;
+
+        Ok(Report {
+            day: self.day,
+            title: self.title,
+            input: input.to_owned(),
+            part_one,
+            part_two,
+            timings,
+        })
+    }
+}
+
+fn solve<D: Day>(
+    input: Input,
+    part: Option<Part>,
+    timings: &mut Timings,
+) -> Result<(Option<Answer>, Option<Answer>)> {
+    let (parsed, parse) = timed(|| D::parse_reader(input));
+    let parsed = parsed?;
+    timings.parse = parse;
+
+    let part_one = match part {
+        None | Some(Part::One) => {
+            let (answer, duration) = timed(|| Answer::new(&D::part_one(&parsed)));
+            timings.part_one = Some(duration);
+            Some(answer)
+        }
+        Some(Part::Two) => None,
+    };
+
+    let part_two = match part {
+        None | Some(Part::Two) => {
+            let (answer, duration) = timed(|| Answer::new(&D::part_two(&parsed)));
+            timings.part_two = Some(duration);
+            Some(answer)
+        }
+        Some(Part::One) => None,
+    };
+
+    Ok((part_one, part_two))
+}
+
+/// Same as [solve], but solves part one on another thread while solving part two
+fn solve_parallel<D: Day>(
+    input: Input,
+    part: Option<Part>,
+    timings: &mut Timings,
+) -> Result<(Option<Answer>, Option<Answer>)>
+where
+    D::Parsed: Sync,
+{
+    if part.is_some() {
+        return solve::<D>(input, part, timings);
+    }
+
+    let (parsed, parse) = timed(|| D::parse_reader(input));
+    let parsed = parsed?;
+    timings.parse = parse;
+
+    let ((part_one, one), (part_two, two)) = thread::scope(|s| {
+        let part_one = s.spawn(|| timed(|| Answer::new(&D::part_one(&parsed))));
+        let part_two = timed(|| Answer::new(&D::part_two(&parsed)));
+
+        // a panicking part should look the same as when solving serially
+        let part_one = part_one.join().unwrap_or_else(|e| panic::resume_unwind(e));
+        (part_one, part_two)
+    });
+
+    timings.part_one = Some(one);
+    timings.part_two = Some(two);
+    Ok((Some(part_one), Some(part_two)))
+}
+
+fn parse_erased<D: Day>(input: Input) -> Result<ParsedInput>
+where
+    D::Parsed: 'static,
+{
+    Ok(ParsedInput(Box::new(D::parse_reader(input)?)))
+}
+
+fn downcast<D: Day>(parsed: &ParsedInput) -> &D::Parsed
+where
+    D::Parsed: 'static,
+{
+    parsed
+        .0
+        .downcast_ref()
+        .unwrap_or_else(|| panic!("input was not parsed by day {:02}", D::DAY))
+}
+
+// the answers go through a black box so that solving can't be optimized away
+fn part_one_erased<D: Day>(parsed: &ParsedInput)
+where
+    D::Parsed: 'static,
+{
+    hint::black_box(D::part_one(downcast::<D>(parsed)));
+}
+
+fn part_two_erased<D: Day>(parsed: &ParsedInput)
+where
+    D::Parsed: 'static,
+{
+    hint::black_box(D::part_two(downcast::<D>(parsed)));
+}
+
+/// The number of threads to use when none is given, which is the number of available cores
+pub fn default_jobs() -> NonZeroUsize {
+    thread::available_parallelism().unwrap_or(NonZeroUsize::MIN)
+}
+
+/// Applies [f] to every item on up to [jobs] threads, returning the results in the same order as
+/// the items
+pub fn parallel_map<T, R>(items: &[T], jobs: NonZeroUsize, f: impl Fn(&T) -> R + Sync) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+{
+    let next = AtomicUsize::new(0);
+    let workers = jobs.get().min(items.len());
+
+    let mut results: Vec<(usize, R)> = thread::scope(|s| {
+        let handles: Vec<_> = (0..workers)
+            .map(|_| {
+                s.spawn(|| {
+                    let mut results = Vec::new();
+
+                    // every worker grabs the next item until there are none left
+                    loop {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(item) = items.get(i) else {
+                            return results;
+                        };
+                        results.push((i, f(item)));
+                    }
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .flat_map(|h| h.join().unwrap_or_else(|e| panic::resume_unwind(e)))
+            .collect()
+    });
+
+    results.sort_unstable_by_key(|(i, _)| *i);
+    results.into_iter().map(|(_, r)| r).collect()
+}
+
+/// Runs every (entry, input) pair on up to [jobs] threads, returning the reports in the same
+/// order as the pairs
+///
+/// The parts of a shared entry are only solved in parallel when there are enough threads for
+/// every run to use 2, so that no more than [jobs] threads are ever busy.
+pub fn run_all(
+    runs: &[(&Entry, String)],
+    part: Option<Part>,
+    jobs: NonZeroUsize,
+) -> Vec<Result<Report>> {
+    let parallel = jobs.get() >= 2 * runs.len();
+
+    parallel_map(runs, jobs, |(entry, input)| {
+        entry.run_with(input, part, parallel)
+    })
+}
+
+/// Every day that has a solution, in order
+///
+/// Days whose parsed input can't be shared between threads are registered with [Entry::new].
+pub static REGISTRY: &[Entry] = &[
+    Entry::shared::<day_01::Day01>(),
+    Entry::shared::<day_02::Day02>(),
+    Entry::shared::<day_03::Day03>(),
+    Entry::shared::<day_04::Day04>(),
+    Entry::shared::<day_05::Day05>(),
+    Entry::shared::<day_06::Day06>(),
+    Entry::new::<day_07::Day07>(),
+    Entry::shared::<day_08::Day08>(),
+    Entry::shared::<day_09::Day09>(),
+    Entry::shared::<day_10::Day10>(),
+    Entry::shared::<day_11::Day11>(),
+    Entry::shared::<day_12::Day12>(),
+    Entry::shared::<day_13::Day13>(),
+    Entry::shared::<day_14::Day14>(),
+    Entry::shared::<day_15::Day15>(),
+    Entry::shared::<day_16::Day16>(),
+    Entry::shared::<day_17::Day17>(),
+    Entry::shared::<day_18::Day18>(),
+    Entry::shared::<day_19::Day19>(),
+    Entry::shared::<day_20::Day20>(),
+    Entry::shared::<day_21::Day21>(),
+    Entry::shared::<day_22::Day22>(),
+    Entry::shared::<day_23::Day23>(),
+    Entry::shared::<day_24::Day24>(),
+    Entry::shared::<day_25::Day25>(),
+];
+
+/// Returns the registered [Entry] for the given [day], if there is one
+pub fn find(day: u8) -> Option<&'static Entry> {
+    REGISTRY.iter().find(|entry| entry.day == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils;
+
+    #[test]
+    fn registry_test() {
+        let days: Vec<_> = REGISTRY.iter().map(|entry| entry.day).collect();
+        let mut sorted = days.clone();
+        sorted.sort_unstable();
+        sorted.dedup();
+
+        assert_eq!(days, sorted);
+    }
+
+    #[test]
+    fn run_test() {
+        let entry = find(12).expect("day 12 should be registered");
+        let input = "inputs/day_12.example";
+
+        let report = entry.run(input, None).expect("could not run day");
+        assert_eq!(report.input, input);
+        assert_eq!(report.part_one.map(|a| a.to_string()), Some("31".into()));
+        assert_eq!(report.part_two.map(|a| a.to_string()), Some("29".into()));
+        assert!(report.timings.part_one.is_some());
+        assert!(report.timings.part_two.is_some());
+
+        let report = entry
+            .run(input, Some(Part::Two))
+            .expect("could not run day");
+        assert_eq!(report.part_one, None);
+        assert_eq!(report.part_two.map(|a| a.to_string()), Some("29".into()));
+        assert_eq!(report.timings.part_one, None);
+
+        let err = entry
+            .run("inputs/day_12.missing", None)
+            .expect_err("should not run");
+        assert!(err
+            .to_string()
+            .contains("could not load inputs/day_12.missing"));
+    }
+
+    #[test]
+    fn parallel_test() {
+        let jobs = NonZeroUsize::new(3).expect("3 is not 0");
+        let squares = parallel_map(&(0..100).collect::<Vec<_>>(), jobs, |i| i * i);
+        assert_eq!(squares, (0..100).map(|i| i * i).collect::<Vec<_>>());
+
+        // the reports come back in order, and the same as when run serially
+        let runs: Vec<_> = [12, 8, 12]
+            .iter()
+            .map(|&day| {
+                let entry = find(day).expect("day should be registered");
+                (entry, format!("inputs/day_{:02}.example", day))
+            })
+            .collect();
+
+        for jobs in [1, 2, 6] {
+            let reports = run_all(&runs, None, NonZeroUsize::new(jobs).expect("jobs is not 0"));
+            let answers: Vec<_> = reports
+                .into_iter()
+                .map(|r| {
+                    let r = r.expect("could not run day");
+                    (
+                        r.day,
+                        r.part_one.map(|a| a.to_string()),
+                        r.part_two.map(|a| a.to_string()),
+                    )
+                })
+                .collect();
+
+            assert_eq!(
+                answers,
+                vec![
+                    (12, Some("31".into()), Some("29".into())),
+                    (8, Some("21".into()), Some("8".into())),
+                    (12, Some("31".into()), Some("29".into())),
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn phases_test() {
+        let entry = find(8).expect("day 8 should be registered");
+        let inputs = entry
+            .inputs(Path::new("inputs"))
+            .expect("could not list inputs");
+        let names: Vec<_> = inputs
+            .iter()
+            .filter_map(|p| p.file_name()?.to_str())
+            .collect();
+        assert_eq!(
+            names,
+            vec!["day_08", "day_08.example", "day_08.large", "day_08.xlarge"]
+        );
+
+        let bytes = utils::load_bytes("inputs/day_08.example").expect("could not load input");
+        let parsed = entry
+            .parse(Input::from_bytes(bytes))
+            .expect("could not parse input");
+        entry.part_one(&parsed);
+        entry.part_two(&parsed);
+
+        let other = find(12).expect("day 12 should be registered");
+        let result = panic::catch_unwind(panic::AssertUnwindSafe(|| other.part_one(&parsed)));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn report_json_test() {
+        let report = find(12)
+            .expect("day 12 should be registered")
+            .run("inputs/day_12.example", None)
+            .expect("could not run day");
+        let json = serde_json::to_value(&report).expect("could not convert report to json");
+
+        assert_eq!(json["day"], 12);
+        assert_eq!(json["input"], "inputs/day_12.example");
+        assert_eq!(json["part_one"], 31);
+        assert_eq!(json["part_two"], 29);
+        assert!(json["timings"]["parse_ns"].is_u64());
+        // reading the input is timed as part of parsing
+        assert!(json["timings"].get("load_ns").is_none());
+    }
+}
@@ -0,0 +1,333 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Context, Result};
+
+use crate::answers::Answers;
+
+const SRC_TEMPLATE: &str = include_str!("../templates/src/day_xx.rs.tpl");
+const EXAMPLE_TEMPLATE: &str = include_str!("../templates/examples/day_xx.rs.tpl");
+
+/// The width rustfmt wraps lines at
+const MAX_WIDTH: usize = 100;
+
+/// Fills in a template for [day]
+///
+/// `{{day}}` becomes the zero padded day, `{{day_number}}` the plain day and `{{title}}` the
+/// title as a string literal.
+pub fn render(template: &str, day: u8, title: &str) -> String {
+    template
+        .replace("{{day}}", &format!("{:02}", day))
+        .replace("{{day_number}}", &day.to_string())
+        .replace("{{title}}", &format!("{:?}", title))
+}
+
+/// Returns the day of a `day_NN` module named in [line], if there is one
+fn day_in(line: &str) -> Option<u8> {
+    let (_, rest) = line.split_once("day_")?;
+    rest.get(..2)?.parse().ok()
+}
+
+/// Inserts [new] into the first block of consecutive lines that [is_item] matches, keeping the
+/// block sorted by the day of each line
+fn insert_sorted(
+    source: &str,
+    day: u8,
+    new: String,
+    is_item: impl Fn(&str) -> bool,
+) -> Result<String> {
+    let mut lines: Vec<&str> = source.lines().collect();
+
+    let Some(start) = lines.iter().position(|l| is_item(l)) else {
+        bail!("could not find where the days are listed");
+    };
+    let end = start + lines[start..].iter().take_while(|l| is_item(l)).count();
+
+    let block = &lines[start..end];
+    if block.iter().any(|l| day_in(l) == Some(day)) {
+        bail!("day {:02} is already listed", day);
+    }
+
+    let at = start
+        + block
+            .iter()
+            .take_while(|l| day_in(l).is_some_and(|d| d < day))
+            .count();
+    lines.insert(at, &new);
+
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Adds `pub mod day_NN;` to `src/lib.rs`
+pub fn register_lib(lib: &str, day: u8) -> Result<String> {
+    insert_sorted(lib, day, format!("pub mod day_{:02};", day), |l| {
+        l.starts_with("pub mod day_")
+    })
+}
+
+/// Adds the day to the imports and the registry of `src/runner.rs`
+pub fn register_runner(runner: &str, day: u8) -> Result<String> {
+    let runner = insert_sorted(
+        runner,
+        day,
+        format!("    Entry::shared::<day_{:02}::Day{:02}>(),", day, day),
+        |l| l.starts_with("    Entry::"),
+    )?;
+
+    // the imported days are wrapped like rustfmt does, so they're rebuilt from scratch
+    let start = runner
+        .match_indices("use crate::{")
+        .map(|(i, _)| i)
+        .find(|&i| {
+            runner[i + "use crate::{".len()..]
+                .trim_start()
+                .starts_with("day_")
+        })
+        .context("could not find the imported days")?;
+    let end = start
+        + runner[start..]
+            .find("};\n")
+            .context("could not find the end of the imported days")?
+        + "};\n".len();
+
+    let mut days: Vec<_> = runner[start..end]
+        .split(|c: char| c == ',' || c.is_whitespace() || c == '{')
+        .filter_map(day_in)
+        .collect();
+    days.push(day);
+    days.sort_unstable();
+
+    let names: Vec<_> = days.iter().map(|d| format!("day_{:02}", d)).collect();
+    Ok(format!(
+        "{}{}{}",
+        &runner[..start],
+        wrap_use(&names),
+        &runner[end..]
+    ))
+}
+
+/// Formats `use crate::{..};` for [names] the way rustfmt would
+fn wrap_use(names: &[String]) -> String {
+    let single = format!("use crate::{{{}}};\n", names.join(", "));
+    if single.len() <= MAX_WIDTH + 1 {
+        return single;
+    }
+
+    let mut lines = vec![String::new()];
+    for name in names {
+        let line = lines.last_mut().expect("there is always a line");
+
+        if line.is_empty() {
+            *line = format!("    {},", name);
+        } else if line.len() + name.len() + 2 <= MAX_WIDTH {
+            *line = format!("{} {},", line, name);
+        } else {
+            lines.push(format!("    {},", name));
+        }
+    }
+
+    format!("use crate::{{\n{}\n}};\n", lines.join("\n"))
+}
+
+/// Everything that `aoc new` writes, so that nothing is touched unless all of it can be done
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Scaffold {
+    /// Files that are created, and refused if they already exist
+    pub created: Vec<(PathBuf, String)>,
+    /// Files that are updated to list the new day
+    pub updated: Vec<(PathBuf, String)>,
+}
+
+impl Scaffold {
+    /// Plans the files for [day] in the repository at [root]
+    pub fn new(root: &Path, day: u8, title: &str) -> Result<Self> {
+        if !(1..=25).contains(&day) {
+            bail!("Invalid day: {}", day);
+        }
+
+        let name = format!("day_{:02}", day);
+        let mut scaffold = Self::default();
+
+        for (dir, template) in [("src", SRC_TEMPLATE), ("examples", EXAMPLE_TEMPLATE)] {
+            let path = root.join(dir).join(format!("{}.rs", name));
+            if path.exists() {
+                bail!("refusing to overwrite {}", path.display());
+            }
+
+            scaffold.created.push((path, render(template, day, title)));
+        }
+
+        // only the example is created if it is missing, so that it can be pasted in. The puzzle
+        // input is left to be downloaded, since `run all` skips the days that don't have one.
+        let inputs = [
+            format!("inputs/{}", name),
+            format!("inputs/{}.example", name),
+        ];
+        let example = root.join(&inputs[1]);
+        if !example.exists() {
+            scaffold.created.push((example, String::new()));
+        }
+
+        let update = |file: &str, f: &dyn Fn(&str) -> Result<String>| -> Result<_> {
+            let path = root.join(file);
+            let source = fs::read_to_string(&path)
+                .with_context(|| format!("could not load {}", path.display()))?;
+            let updated = f(&source).with_context(|| format!("could not update {}", file))?;
+            Ok((path, updated))
+        };
+
+        scaffold
+            .updated
+            .push(update("src/lib.rs", &|s| register_lib(s, day))?);
+        scaffold
+            .updated
+            .push(update("src/runner.rs", &|s| register_runner(s, day))?);
+        scaffold.updated.push(update("answers.json", &|s| {
+            let mut answers: Answers = s.parse()?;
+            for input in &inputs {
+                answers.add_stub(day, input);
+            }
+            Ok(format!("{}\n", answers))
+        })?);
+
+        Ok(scaffold)
+    }
+
+    /// Writes every file, returning the paths that were written
+    pub fn write(&self) -> Result<Vec<&Path>> {
+        let mut written = Vec::new();
+
+        for (path, contents) in self.created.iter().chain(&self.updated) {
+            fs::write(path, contents)
+                .with_context(|| format!("could not write {}", path.display()))?;
+            written.push(path.as_path());
+        }
+
+        Ok(written)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_test() {
+        let rendered = render(SRC_TEMPLATE, 7, "no \"space\" left");
+
+        assert!(rendered.contains("pub struct Day07;"));
+        assert!(rendered.contains("const DAY: u8 = 7;"));
+        assert!(rendered.contains(r#"const TITLE: &'static str = "no \"space\" left";"#));
+        assert!(rendered.contains("inputs/day_07.example"));
+        assert!(rendered.contains("#[ignore"));
+        assert!(!rendered.contains("todo!()"));
+        assert!(!rendered.contains("{{"));
+    }
+
+    #[test]
+    fn register_test() {
+        let lib = "pub mod utils;\n\npub mod day_01;\npub mod day_12;\n";
+        assert_eq!(
+            register_lib(lib, 3).expect("could not register"),
+            "pub mod utils;\n\npub mod day_01;\npub mod day_03;\npub mod day_12;\n"
+        );
+        assert_eq!(
+            register_lib(lib, 24).expect("could not register"),
+            "pub mod utils;\n\npub mod day_01;\npub mod day_12;\npub mod day_24;\n"
+        );
+        assert!(register_lib(lib, 12).is_err());
+        assert!(register_lib("pub mod utils;\n", 12).is_err());
+    }
+
+    #[test]
+    fn register_runner_test() {
+        let days: Vec<_> = (1..=25)
+            .filter(|&d| d != 16)
+            .map(|d| format!("day_{:02}", d))
+            .collect();
+        let entries: String = days
+            .iter()
+            .map(|d| format!("    Entry::new::<{}::Day{}>(),\n", d, &d[4..]))
+            .collect();
+        let runner = format!(
+            "{}use crate::solution::Day;\n\npub static REGISTRY: &[Entry] = &[\n{}];\n",
+            wrap_use(&days),
+            entries
+        );
+
+        let updated = register_runner(&runner, 16).expect("could not register");
+        assert!(updated.contains("day_15, day_16, day_17"));
+        assert!(updated.contains(
+            "    Entry::new::<day_15::Day15>(),\n    Entry::shared::<day_16::Day16>(),\n"
+        ));
+        assert!(updated.contains("use crate::solution::Day;"));
+        assert!(register_runner(&updated, 16).is_err());
+    }
+
+    #[test]
+    fn wrap_use_test() {
+        let names: Vec<_> = (1..=3).map(|d| format!("day_{:02}", d)).collect();
+        assert_eq!(wrap_use(&names), "use crate::{day_01, day_02, day_03};\n");
+
+        let names: Vec<_> = (1..=13).map(|d| format!("day_{:02}", d)).collect();
+        assert_eq!(
+            wrap_use(&names),
+            "use crate::{\n    day_01, day_02, day_03, day_04, day_05, day_06, day_07, day_08, day_09, day_10, day_11, day_12,\n    day_13,\n};\n"
+        );
+    }
+
+    #[test]
+    fn scaffold_test() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        for dir in ["src", "examples", "inputs"] {
+            fs::create_dir_all(root.join(dir)).expect("could not create dir");
+        }
+
+        let files = [
+            ("src/lib.rs", "pub mod day_01;\n"),
+            (
+                "src/runner.rs",
+                "use crate::{\n    day_01,\n};\n\nstatic REGISTRY: &[Entry] = &[\n    Entry::new::<day_01::Day01>(),\n];\n",
+            ),
+            ("answers.json", "{}\n"),
+            ("inputs/day_02", "downloaded\n"),
+        ];
+        for (file, contents) in files {
+            fs::write(root.join(file), contents).expect("could not write file");
+        }
+
+        let scaffold = Scaffold::new(&root, 2, "rock paper scissors").expect("could not plan day");
+        scaffold.write().expect("could not write day");
+
+        let read = |file: &str| fs::read_to_string(root.join(file)).expect("could not read file");
+        assert!(read("src/day_02.rs").contains("pub struct Day02;"));
+        assert!(read("examples/day_02.rs").contains("Day02::solve"));
+        assert_eq!(read("src/lib.rs"), "pub mod day_01;\npub mod day_02;\n");
+        assert!(read("src/runner.rs").contains("use crate::{day_01, day_02};"));
+        assert!(read("answers.json").contains("\"inputs/day_02.example\": {}"));
+
+        // the downloaded input is kept, and nothing is overwritten the second time around
+        assert_eq!(read("inputs/day_02"), "downloaded\n");
+        assert_eq!(read("inputs/day_02.example"), "");
+        assert!(!Scaffold::new(&root, 3, "no input")
+            .expect("could not plan day")
+            .created
+            .iter()
+            .any(|(path, _)| path.ends_with("inputs/day_03")));
+        let err = Scaffold::new(&root, 2, "rock paper scissors").expect_err("should refuse");
+        assert!(err.to_string().contains("refusing to overwrite"));
+        assert!(Scaffold::new(&root, 26, "no such day").is_err());
+
+        fs::remove_dir_all(&root).expect("could not clean up");
+    }
+
+    #[test]
+    fn answers_test() {
+        // rewriting the answers keeps them as they are, apart from the new stubs
+        let answers = fs::read_to_string("answers.json").expect("could not load answers");
+        let reparsed: Answers = answers.parse().expect("could not parse answers");
+        assert_eq!(format!("{}\n", reparsed), answers);
+    }
+}
@@ -0,0 +1,287 @@
+//! Graph searches over any node type
+//!
+//! The graph is never built up front. Each search takes the nodes to start from, a closure that
+//! returns the neighbours of a node (with the cost of moving there for the weighted searches) and
+//! a predicate that says whether a node is a goal.
+
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+use rustc_hash::{FxHashMap, FxHashSet};
+
+/// A path found by a search, from one of the starts to a goal, both included
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Path<N, C = usize> {
+    pub cost: C,
+    pub nodes: Vec<N>,
+}
+
+impl<N, C> Path<N, C> {
+    /// Returns the node the path ends at
+    pub fn goal(&self) -> &N {
+        self.nodes.last().expect("a path is never empty")
+    }
+}
+
+/// Walks the [parents] back from [goal] to a start
+fn reconstruct<N>(parents: &FxHashMap<N, N>, goal: N) -> Vec<N>
+where
+    N: Copy + Eq + Hash,
+{
+    let mut nodes = vec![goal];
+    let mut current = goal;
+
+    while let Some(&parent) = parents.get(&current) {
+        nodes.push(parent);
+        current = parent;
+    }
+
+    nodes.reverse();
+    nodes
+}
+
+/// Finds the path with the fewest steps from any of the [starts] to a node satisfying [is_goal]
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N>>
+where
+    N: Copy + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut visited = FxHashSet::default();
+    let mut parents = FxHashMap::default();
+    let mut q = VecDeque::default();
+
+    for start in starts {
+        if visited.insert(start) {
+            q.push_back((start, 0));
+        }
+    }
+
+    while let Some((node, dist)) = q.pop_front() {
+        if is_goal(&node) {
+            return Some(Path {
+                cost: dist,
+                nodes: reconstruct(&parents, node),
+            });
+        }
+
+        for neighbour in neighbours(&node) {
+            if visited.insert(neighbour) {
+                parents.insert(neighbour, node);
+                q.push_back((neighbour, dist + 1));
+            }
+        }
+    }
+
+    None
+}
+
+/// Finds the cheapest path from any of the [starts] to a node satisfying [is_goal]. The
+/// [neighbours] closure returns each neighbour together with the cost of moving to it.
+pub fn dijkstra<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    neighbours: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, C>>
+where
+    N: Copy + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(starts, neighbours, |_| C::default(), is_goal)
+}
+
+/// Like [dijkstra], but guided by a [heuristic] that estimates the remaining cost from a node to
+/// the nearest goal.
+///
+/// The heuristic must be consistent: it is 0 on a goal and never drops by more than the cost of
+/// a step from a node to its neighbour. Never overestimating isn't enough, since a node is never
+/// expanded again once it has been, so a cheaper path to it found later would be missed.
+pub fn astar<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, C>>
+where
+    N: Copy + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut costs: FxHashMap<N, C> = FxHashMap::default();
+    let mut parents = FxHashMap::default();
+    let mut visited = FxHashSet::default();
+    let mut q = BinaryHeap::default();
+
+    for start in starts {
+        costs.insert(start, C::default());
+        q.push(State {
+            priority: heuristic(&start),
+            cost: C::default(),
+            node: start,
+        });
+    }
+
+    while let Some(State { cost, node, .. }) = q.pop() {
+        if !visited.insert(node) {
+            continue;
+        }
+
+        if is_goal(&node) {
+            return Some(Path {
+                cost,
+                nodes: reconstruct(&parents, node),
+            });
+        }
+
+        for (neighbour, step) in neighbours(&node) {
+            let next = cost + step;
+
+            if costs.get(&neighbour).is_none_or(|&c| next < c) {
+                costs.insert(neighbour, next);
+                parents.insert(neighbour, node);
+                q.push(State {
+                    priority: next + heuristic(&neighbour),
+                    cost: next,
+                    node: neighbour,
+                });
+            }
+        }
+    }
+
+    None
+}
+
+/// Returns every node reachable from the [starts], the starts included
+pub fn flood_fill<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+) -> FxHashSet<N>
+where
+    N: Copy + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut visited = FxHashSet::default();
+    let mut stack = Vec::default();
+
+    for start in starts {
+        if visited.insert(start) {
+            stack.push(start);
+        }
+    }
+
+    while let Some(node) = stack.pop() {
+        for neighbour in neighbours(&node) {
+            if visited.insert(neighbour) {
+                stack.push(neighbour);
+            }
+        }
+    }
+
+    visited
+}
+
+/// A node in the priority queue, ordered so that the lowest priority is popped first
+struct State<N, C> {
+    priority: C,
+    cost: C,
+    node: N,
+}
+
+impl<N, C: Ord> PartialEq for State<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<N, C: Ord> Eq for State<N, C> {}
+
+impl<N, C: Ord> PartialOrd for State<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> Ord for State<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a 5x5 grid with a wall down the middle that has a gap at the bottom
+    fn open(p: &(i32, i32)) -> bool {
+        (0..5).contains(&p.0) && (0..5).contains(&p.1) && (p.1 != 2 || p.0 == 4)
+    }
+
+    fn neighbours(p: &(i32, i32)) -> Vec<(i32, i32)> {
+        [(0, 1), (1, 0), (0, -1), (-1, 0)]
+            .iter()
+            .map(|d| (p.0 + d.0, p.1 + d.1))
+            .filter(open)
+            .collect()
+    }
+
+    #[test]
+    fn bfs_test() {
+        let path = bfs([(0, 0)], neighbours, |p| *p == (0, 4)).expect("no path found");
+        assert_eq!(path.cost, 12);
+        assert_eq!(path.nodes.len(), 13);
+        assert_eq!(path.nodes[0], (0, 0));
+        assert_eq!(*path.goal(), (0, 4));
+        assert!(path.nodes.contains(&(4, 2)));
+
+        // starting on the goal is a path of no steps
+        let path = bfs([(3, 3), (0, 0)], neighbours, |p| *p == (0, 0)).expect("no path found");
+        assert_eq!(path.cost, 0);
+        assert_eq!(path.nodes, vec![(0, 0)]);
+
+        assert_eq!(bfs([(0, 0)], neighbours, |p| *p == (0, 2)), None);
+    }
+
+    #[test]
+    fn dijkstra_test() {
+        // moving down costs 10, so it is cheaper to go around when possible
+        let weighted = |p: &(i32, i32)| {
+            neighbours(p)
+                .into_iter()
+                .map(|q| (q, if q.0 > p.0 { 10 } else { 1 }))
+                .collect::<Vec<_>>()
+        };
+
+        let path = dijkstra([(0, 0)], weighted, |p| *p == (4, 0)).expect("no path found");
+        assert_eq!(path.cost, 40);
+        assert_eq!(path.nodes.len(), 5);
+
+        let path = dijkstra([(0, 0)], weighted, |p| *p == (0, 4)).expect("no path found");
+        assert_eq!(path.cost, 48);
+    }
+
+    #[test]
+    fn astar_test() {
+        let unit = |p: &(i32, i32)| neighbours(p).into_iter().map(|q| (q, 1));
+        let manhattan = |p: &(i32, i32)| p.0.abs_diff(0) + p.1.abs_diff(4);
+
+        let path = astar([(0, 0)], unit, manhattan, |p| *p == (0, 4)).expect("no path found");
+        let expected = bfs([(0, 0)], neighbours, |p| *p == (0, 4)).expect("no path found");
+        assert_eq!(path.cost as usize, expected.cost);
+        assert_eq!(*path.goal(), (0, 4));
+    }
+
+    #[test]
+    fn flood_fill_test() {
+        let reached = flood_fill([(0, 0)], neighbours);
+        assert_eq!(reached.len(), 21);
+        assert!(!reached.contains(&(0, 2)));
+    }
+}
@@ -1,12 +1,15 @@
 use std::{env, fmt::Display};
 
+use anyhow::Result;
 use serde::Serialize;
 
+use crate::utils::Input;
+
 #[derive(Debug, Serialize, PartialEq)]
 pub struct Solution<T, G>
 where
-    T: Display + Serialize + PartialEq,
-    G: Display + Serialize + PartialEq,
+    T: Display + Serialize,
+    G: Display + Serialize,
 {
     pub part_one: T,
     pub part_two: G,
@@ -14,8 +17,8 @@ where
 
 impl<T, G> Display for Solution<T, G>
 where
-    T: Display + Serialize + PartialEq,
-    G: Display + Serialize + PartialEq,
+    T: Display + Serialize,
+    G: Display + Serialize,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         if env::var("AOC_OUTPUT_JSON").is_ok() {
@@ -29,3 +32,90 @@ where
         }
     }
 }
+
+/// An answer that only exists if the goal of the puzzle can be reached, like the length of a
+/// route. It is shown as `unreachable` and converted to `null` in json when there is none.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
+#[serde(transparent)]
+pub struct Reachable<T>(pub Option<T>);
+
+impl<T: Display> Display for Reachable<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.0 {
+            Some(answer) => answer.fmt(f),
+            None => f.write_str("unreachable"),
+        }
+    }
+}
+
+impl<T> From<Option<T>> for Reachable<T> {
+    fn from(answer: Option<T>) -> Self {
+        Self(answer)
+    }
+}
+
+/// A single day's puzzle: how to parse the input and how to solve both parts of it
+pub trait Day {
+    const DAY: u8;
+    const TITLE: &'static str;
+
+    type Parsed;
+    type PartOne: Display + Serialize;
+    type PartTwo: Display + Serialize;
+
+    fn parse(lines: &[String]) -> Result<Self::Parsed>;
+
+    /// Parses straight from the [input]. By default this collects the lines and defers to
+    /// [Day::parse], days with large inputs can override it to avoid allocating every line.
+    fn parse_reader(input: Input) -> Result<Self::Parsed> {
+        let lines = input.lines().collect::<Result<Vec<_>, _>>()?;
+        Self::parse(&lines)
+    }
+
+    fn part_one(parsed: &Self::Parsed) -> Self::PartOne;
+
+    fn part_two(parsed: &Self::Parsed) -> Self::PartTwo;
+
+    /// Parses the [lines] and solves both parts
+    fn solve(lines: &[String]) -> Result<Solution<Self::PartOne, Self::PartTwo>> {
+        let parsed = Self::parse(lines)?;
+
+        Ok(Solution {
+            part_one: Self::part_one(&parsed),
+            part_two: Self::part_two(&parsed),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{day_05::Day05, utils};
+
+    #[test]
+    fn solve_test() {
+        let lines = utils::load_input("inputs/day_05.example").expect("could not load input");
+        let solution = Day05::solve(&lines).expect("could not parse input");
+
+        assert_eq!(
+            solution,
+            Solution {
+                part_one: "CMZ".to_owned(),
+                part_two: "MCD".to_owned(),
+            }
+        );
+    }
+
+    #[test]
+    fn reachable_test() {
+        assert_eq!(Reachable(Some(31)).to_string(), "31");
+        assert_eq!(Reachable::<usize>(None).to_string(), "unreachable");
+        assert_eq!(
+            serde_json::to_value(Reachable(Some(31))).expect("could not convert to json"),
+            31
+        );
+        assert!(serde_json::to_value(Reachable::<usize>(None))
+            .expect("could not convert to json")
+            .is_null());
+    }
+}
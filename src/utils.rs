@@ -1,16 +1,177 @@
 use std::{
-    env,
+    env, error, fmt,
     fs::File,
-    io::{self, BufRead, BufReader},
+    io::{self, BufRead, BufReader, Cursor, Read},
+    str::FromStr,
 };
 
+use crate::parser;
+
+/// An error pointing at the spot in a puzzle input that could not be parsed
+///
+/// Parsers of a single line only know the column, the line number is attached by whoever splits
+/// the input into lines (see [parse_lines] and [parse_blocks]) and the path by the runner.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct ParseError {
+    pub path: Option<String>,
+    /// 1-based line number
+    pub line: Option<usize>,
+    /// 1-based column, in bytes
+    pub column: Option<usize>,
+    pub expected: String,
+}
+
+impl ParseError {
+    pub fn new(expected: impl Into<String>) -> Self {
+        Self {
+            expected: expected.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Points the error at the 0-based byte [offset] into its line
+    pub fn at_offset(mut self, offset: usize) -> Self {
+        self.column = Some(offset + 1);
+        self
+    }
+
+    /// Points the error at the 0-based [offset] into the input. If the error already has a line,
+    /// it is taken as relative to [offset].
+    pub fn at_line(mut self, offset: usize) -> Self {
+        self.line = Some(offset + self.line.unwrap_or(1));
+        self
+    }
+
+    /// Shifts the column of the error by [offset] bytes, for errors from parsing a substring
+    pub fn shift(mut self, offset: usize) -> Self {
+        self.column = Some(offset + self.column.unwrap_or(1));
+        self
+    }
+
+    pub fn with_path(mut self, path: &str) -> Self {
+        self.path = Some(path.to_owned());
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let location = [
+            self.path.clone(),
+            self.line.map(|l| l.to_string()),
+            self.column.map(|c| c.to_string()),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(":");
+
+        if !location.is_empty() {
+            write!(f, "{}: ", location)?;
+        }
+
+        write!(f, "expected {}", self.expected)
+    }
+}
+
+impl error::Error for ParseError {}
+
+/// Parses every line with [FromStr], numbering the lines in any errors
+pub fn parse_lines<T>(lines: &[String]) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr<Err = ParseError>,
+{
+    lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| line.parse().map_err(|e: ParseError| e.at_line(i)))
+        .collect()
+}
+
+/// Parses every block of lines separated by an empty line, numbering the lines in any errors
+pub fn parse_blocks<'a, T>(lines: &'a [String]) -> Result<Vec<T>, ParseError>
+where
+    T: TryFrom<&'a [String], Error = ParseError>,
+{
+    parser::blocks(lines)
+        .map(|(start, block)| T::try_from(block).map_err(|e| e.at_line(start)))
+        .collect()
+}
+
+/// A puzzle input that is read line by line instead of all at once
+///
+/// Lines can be read as owned strings with [Input::lines], or borrowed as raw bytes with
+/// [Input::next_line], which reuses one buffer for the whole input.
+pub struct Input {
+    reader: Box<dyn BufRead>,
+    buf: Vec<u8>,
+}
+
+impl Input {
+    /// Opens the file at [path] for reading, or stdin if the path is `-`
+    pub fn open(path: &str) -> Result<Self, io::Error> {
+        if path == "-" {
+            return Ok(Self::from_reader(BufReader::new(io::stdin())));
+        }
+
+        Ok(Self::from_reader(BufReader::new(File::open(path)?)))
+    }
+
+    pub fn from_reader(reader: impl BufRead + 'static) -> Self {
+        Self {
+            reader: Box::new(reader),
+            buf: Vec::default(),
+        }
+    }
+
+    pub fn from_bytes(bytes: Vec<u8>) -> Self {
+        Self::from_reader(Cursor::new(bytes))
+    }
+
+    /// Reads the next line without its line ending, or `None` at the end of the input. The
+    /// returned slice is only valid until the next call.
+    pub fn next_line(&mut self) -> Result<Option<&[u8]>, io::Error> {
+        self.buf.clear();
+
+        if self.reader.read_until(b'\n', &mut self.buf)? == 0 {
+            return Ok(None);
+        }
+
+        if self.buf.last() == Some(&b'\n') {
+            self.buf.pop();
+
+            if self.buf.last() == Some(&b'\r') {
+                self.buf.pop();
+            }
+        }
+
+        Ok(Some(&self.buf))
+    }
+
+    pub fn lines(self) -> impl Iterator<Item = Result<String, io::Error>> {
+        self.reader.lines()
+    }
+
+    pub fn bytes(self) -> impl Iterator<Item = Result<u8, io::Error>> {
+        self.reader.bytes()
+    }
+}
+
 pub fn load_input(default_path: &str) -> Result<Vec<String>, io::Error> {
     let path = env::var("AOC_INPUT").unwrap_or(default_path.to_string());
     load_lines(&path)
 }
 
+/// Loads every line of the input at [path], which may be `-` for stdin
 pub fn load_lines(path: &str) -> Result<Vec<String>, io::Error> {
-    BufReader::new(File::open(path)?).lines().collect()
+    Input::open(path)?.lines().collect()
+}
+
+/// Loads the whole input at [path] into memory without splitting it into lines
+pub fn load_bytes(path: &str) -> Result<Vec<u8>, io::Error> {
+    let mut bytes = Vec::default();
+    Input::open(path)?.reader.read_to_end(&mut bytes)?;
+    Ok(bytes)
 }
 
 pub fn split_lines(s: &str) -> Vec<String> {
@@ -22,6 +183,7 @@ pub fn split_lines(s: &str) -> Vec<String> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::parser::{integer, literal, separated_list, Parser};
 
     #[test]
     fn split_lines_test() {
@@ -42,4 +204,59 @@ mod tests {
 
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn parse_error_test() {
+        let lines = vec!["1 2".to_string(), "3 x".to_string()];
+        let actual = parse_lines::<Pair>(&lines)
+            .unwrap_err()
+            .with_path("inputs/day_xx");
+        assert_eq!(actual.to_string(), "inputs/day_xx:2:3: expected a number");
+
+        let lines = split_lines("1 2\n\n3 4\nfive 6");
+        let actual = parse_blocks::<Pairs>(&lines).unwrap_err();
+        assert_eq!(actual.line, Some(4));
+        assert_eq!(actual.column, Some(1));
+    }
+
+    #[derive(Debug)]
+    struct Pair;
+
+    impl FromStr for Pair {
+        type Err = ParseError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            separated_list(integer::<u8>(), literal(" ")).parse_line(s)?;
+            Ok(Self)
+        }
+    }
+
+    #[derive(Debug)]
+    struct Pairs;
+
+    impl TryFrom<&[String]> for Pairs {
+        type Error = ParseError;
+
+        fn try_from(lines: &[String]) -> Result<Self, Self::Error> {
+            parse_lines::<Pair>(lines)?;
+            Ok(Self)
+        }
+    }
+
+    #[test]
+    fn input_test() {
+        let mut input = Input::from_bytes(b"30373\r\n25512\n\n65332".to_vec());
+
+        assert_eq!(input.next_line().unwrap(), Some(&b"30373"[..]));
+        assert_eq!(input.next_line().unwrap(), Some(&b"25512"[..]));
+        assert_eq!(input.next_line().unwrap(), Some(&b""[..]));
+        assert_eq!(input.next_line().unwrap(), Some(&b"65332"[..]));
+        assert_eq!(input.next_line().unwrap(), None);
+
+        let lines = Input::from_bytes(b"a\nb\n".to_vec())
+            .lines()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(lines, vec!["a".to_string(), "b".to_string()]);
+    }
 }
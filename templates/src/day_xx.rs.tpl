@@ -1,32 +1,59 @@
 use anyhow::Result;
 
-pub fn parse_input(lines: &[String]) -> Result<Vec<_>> {
-    todo!()
+use crate::solution::Day;
+
+pub fn parse_input(lines: &[String]) -> Result<Vec<String>> {
+    Ok(lines.to_vec())
 }
 
-pub fn part_one(parsed: &Vec<_>) -> usize {
-    todo!()
+pub fn part_one(_parsed: &[String]) -> usize {
+    0
 }
 
-pub fn part_two(parsed: &Vec<_>) -> usize {
-    todo!()
+pub fn part_two(_parsed: &[String]) -> usize {
+    0
+}
+
+pub struct Day{{day}};
+
+impl Day for Day{{day}} {
+    const DAY: u8 = {{day_number}};
+    const TITLE: &'static str = {{title}};
+
+    type Parsed = Vec<String>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(lines: &[String]) -> Result<Self::Parsed> {
+        parse_input(lines)
+    }
+
+    fn part_one(parsed: &Self::Parsed) -> Self::PartOne {
+        part_one(parsed)
+    }
+
+    fn part_two(parsed: &Self::Parsed) -> Self::PartTwo {
+        part_two(parsed)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::utils;
     use super::*;
+    use crate::utils;
 
     #[test]
+    #[ignore = "the example answer is not known yet"]
     fn part_one_test() {
-        let lines = utils::load_input("inputs/day_xx.example").expect("could not load input");
+        let lines = utils::load_input("inputs/day_{{day}}.example").expect("could not load input");
         let parsed = parse_input(&lines).expect("could not parse input");
         assert_eq!(part_one(&parsed), 0);
     }
 
     #[test]
+    #[ignore = "the example answer is not known yet"]
     fn part_two_test() {
-        let lines = utils::load_input("inputs/day_xx.example").expect("could not load input");
+        let lines = utils::load_input("inputs/day_{{day}}.example").expect("could not load input");
         let parsed = parse_input(&lines).expect("could not parse input");
         assert_eq!(part_two(&parsed), 0);
     }